@@ -0,0 +1,145 @@
+use super::{Check, CheckInfo};
+use crate::Governance;
+use crate::model::{Diagnostic, EntityKey, Span};
+use std::collections::BTreeMap;
+
+/// One entity's use of a value that should be unique across entities.
+struct Occurrence<'a> {
+    key: &'a EntityKey,
+    value: &'a str,
+    span: Option<Span>,
+}
+
+/// Groups occurrences by their normalized value and reports each one that
+/// shares its value with another entity, naming every other file involved.
+fn collisions<'a>(
+    check: &dyn CheckInfo,
+    what: &str,
+    occurrences: impl Iterator<Item = (String, Occurrence<'a>)>,
+) -> Vec<Diagnostic> {
+    let mut groups: BTreeMap<String, Vec<Occurrence>> = BTreeMap::new();
+    for (normalized, occurrence) in occurrences {
+        let group = groups.entry(normalized).or_default();
+        // Repeats within one file are left to the list checks
+        if !group.iter().any(|o| o.key == occurrence.key) {
+            group.push(occurrence);
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for mut group in groups.into_values().filter(|g| g.len() > 1) {
+        group.sort_by_key(|o| o.key);
+        for occurrence in &group {
+            let others = group
+                .iter()
+                .filter(|o| o.key != occurrence.key)
+                .map(|o| o.key.file())
+                .collect::<Vec<_>>();
+            diagnostics.push(check.diagnostic(
+                occurrence.key.file(),
+                format!(
+                    "{} '{}' is also used in {}",
+                    what,
+                    occurrence.value,
+                    others.join(", ")
+                ),
+                occurrence.span,
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+pub struct DuplicateGithubUsers;
+
+impl CheckInfo for DuplicateGithubUsers {
+    fn id(&self) -> &'static str {
+        "duplicate-github-user"
+    }
+
+    fn description(&self) -> &'static str {
+        "GitHub usernames must be unique across contributors, ignoring case"
+    }
+}
+
+impl Check for DuplicateGithubUsers {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        // GitHub logins are case-insensitive
+        let occurrences = governance.contributors().map(|(key, contributor)| {
+            (
+                contributor.github_username.as_str().to_ascii_lowercase(),
+                Occurrence {
+                    key,
+                    value: contributor.github_username.as_str(),
+                    span: governance.field_span(key, "github-username"),
+                },
+            )
+        });
+
+        collisions(self, "GitHub username", occurrences)
+    }
+}
+
+pub struct DuplicateSlackMembers;
+
+impl CheckInfo for DuplicateSlackMembers {
+    fn id(&self) -> &'static str {
+        "duplicate-slack-member"
+    }
+
+    fn description(&self) -> &'static str {
+        "Slack member IDs must be unique across contributors"
+    }
+}
+
+impl Check for DuplicateSlackMembers {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let occurrences = governance.contributors().map(|(key, contributor)| {
+            (
+                contributor.slack_member_id.to_string(),
+                Occurrence {
+                    key,
+                    value: contributor.slack_member_id.as_str(),
+                    span: governance.field_span(key, "slack-member-id"),
+                },
+            )
+        });
+
+        collisions(self, "Slack member ID", occurrences)
+    }
+}
+
+pub struct DuplicateSlackChannels;
+
+impl CheckInfo for DuplicateSlackChannels {
+    fn id(&self) -> &'static str {
+        "duplicate-slack-channel"
+    }
+
+    fn description(&self) -> &'static str {
+        "Slack channel IDs must not be shared between teams"
+    }
+}
+
+impl Check for DuplicateSlackChannels {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let occurrences = governance.teams().flat_map(|(key, team)| {
+            team.slack_channel_ids
+                .iter()
+                .enumerate()
+                .map(move |(index, channel_id)| {
+                    (
+                        channel_id.to_string(),
+                        Occurrence {
+                            key,
+                            value: channel_id.as_str(),
+                            span: governance.item_span(key, "slack-channel-ids", index),
+                        },
+                    )
+                })
+        });
+
+        collisions(self, "Slack channel ID", occurrences)
+    }
+}
@@ -0,0 +1,65 @@
+use super::{Check, CheckInfo};
+use crate::Governance;
+use crate::model::Diagnostic;
+
+pub struct FileNames;
+
+impl CheckInfo for FileNames {
+    fn id(&self) -> &'static str {
+        "file-name-mismatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "File names must match the GitHub username, team name or repo name they define"
+    }
+}
+
+impl Check for FileNames {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        // Validate contributor filenames match GitHub usernames
+        for (key, contributor) in governance.contributors() {
+            if contributor.github_username != key.name {
+                diagnostics.push(self.diagnostic(
+                    key.file(),
+                    format!(
+                        "Contributor file name '{}' doesn't match GitHub username '{}'",
+                        key.name, contributor.github_username
+                    ),
+                    governance.field_span(key, "github-username"),
+                ));
+            }
+        }
+
+        // Validate team filenames match team names
+        for (key, team) in governance.teams() {
+            if key.name != team.name {
+                diagnostics.push(self.diagnostic(
+                    key.file(),
+                    format!(
+                        "Team file name '{}' doesn't match team name '{}'",
+                        key.name, team.name
+                    ),
+                    governance.field_span(key, "name"),
+                ));
+            }
+        }
+
+        // Validate repo filenames match repo names
+        for (key, repo) in governance.repos() {
+            if key.name != repo.name {
+                diagnostics.push(self.diagnostic(
+                    key.file(),
+                    format!(
+                        "Repo file name '{}' doesn't match repo name '{}'",
+                        key.name, repo.name
+                    ),
+                    governance.field_span(key, "name"),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
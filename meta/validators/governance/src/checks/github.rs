@@ -0,0 +1,63 @@
+use super::{AsyncCheck, CheckInfo};
+use crate::Governance;
+use crate::model::Diagnostic;
+use crate::remote::GithubClient;
+use futures::future::BoxFuture;
+
+pub struct GithubUsers {
+    client: GithubClient,
+}
+
+impl GithubUsers {
+    pub fn new(client: GithubClient) -> Self {
+        Self { client }
+    }
+}
+
+impl CheckInfo for GithubUsers {
+    fn id(&self) -> &'static str {
+        "unknown-github-user"
+    }
+
+    fn description(&self) -> &'static str {
+        "Contributor GitHub usernames must exist on GitHub"
+    }
+}
+
+/// Runs without a token too: user profiles are public, so the client looks
+/// each user up over REST instead, within GitHub's lower anonymous rate limit.
+impl AsyncCheck for GithubUsers {
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let mut diagnostics = Vec::new();
+
+            let contributors = governance.contributors().collect::<Vec<_>>();
+            let logins = contributors
+                .iter()
+                .map(|(_, contributor)| &contributor.github_username)
+                .collect::<Vec<_>>();
+            let results = self.client.users_exist(&logins).await;
+
+            for ((contributor_id, contributor), result) in contributors.into_iter().zip(results) {
+                let github = &contributor.github_username;
+                let file = contributor_id.file();
+                let span = governance.field_span(contributor_id, "github-username");
+                match result {
+                    Ok(true) => {}
+                    Ok(false) => diagnostics.push(self.diagnostic(
+                        file,
+                        format!("GitHub user does not exist: {}", github),
+                        span,
+                    )),
+                    Err(e) => diagnostics.push(self.inconclusive(
+                        file,
+                        format!("Failed to check GitHub user {}: {}", github, e),
+                        span,
+                    )),
+                }
+            }
+
+            diagnostics
+        })
+    }
+}
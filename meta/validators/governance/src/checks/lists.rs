@@ -0,0 +1,133 @@
+use super::{Check, CheckInfo};
+use crate::Governance;
+use crate::model::{Diagnostic, Severity, Team};
+use std::collections::HashMap;
+
+/// The list fields of a team as `(field, noun, entries)`.
+fn team_lists(team: &Team) -> [(&'static str, &'static str, Vec<&str>); 3] {
+    [
+        (
+            "members",
+            "member",
+            team.members.iter().map(String::as_str).collect(),
+        ),
+        (
+            "repos",
+            "repo",
+            team.repos.iter().map(String::as_str).collect(),
+        ),
+        (
+            "slack-channel-ids",
+            "Slack channel",
+            team.slack_channel_ids
+                .iter()
+                .map(|id| id.as_str())
+                .collect(),
+        ),
+    ]
+}
+
+pub struct DuplicateEntries;
+
+impl CheckInfo for DuplicateEntries {
+    fn id(&self) -> &'static str {
+        "duplicate-entry"
+    }
+
+    fn description(&self) -> &'static str {
+        "Team member, repo and Slack channel lists must not repeat an entry, even in a different case"
+    }
+}
+
+impl Check for DuplicateEntries {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (team_key, team) in governance.teams() {
+            for (field, noun, entries) in team_lists(team) {
+                let mut seen: HashMap<String, usize> = HashMap::new();
+                for (index, entry) in entries.iter().enumerate() {
+                    let Some(&first) = seen.get(&entry.to_lowercase()) else {
+                        seen.insert(entry.to_lowercase(), index);
+                        continue;
+                    };
+
+                    let first_at = governance
+                        .item_span(team_key, field, first)
+                        .map(|span| format!(" (first at {})", span))
+                        .unwrap_or_default();
+                    let message = if entries[first] == *entry {
+                        format!(
+                            "Team '{}' lists {} '{}' more than once{}",
+                            team_key.name, noun, entry, first_at
+                        )
+                    } else {
+                        format!(
+                            "Team '{}' lists {} '{}', a case variant of '{}'{}",
+                            team_key.name, noun, entry, entries[first], first_at
+                        )
+                    };
+
+                    diagnostics.push(self.diagnostic(
+                        team_key.file(),
+                        message,
+                        governance.item_span(team_key, field, index),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+pub struct UnsortedLists;
+
+impl CheckInfo for UnsortedLists {
+    fn id(&self) -> &'static str {
+        "unsorted-list"
+    }
+
+    fn description(&self) -> &'static str {
+        "Team member, repo and Slack channel lists should be sorted, for cleaner diffs"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+impl Check for UnsortedLists {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (team_key, team) in governance.teams() {
+            for (field, _, entries) in team_lists(team) {
+                // Report the first entry that sorts before its predecessor
+                let unsorted = entries
+                    .windows(2)
+                    .position(|pair| pair[1].to_lowercase() < pair[0].to_lowercase());
+
+                if let Some(index) = unsorted {
+                    diagnostics.push(self.diagnostic(
+                        team_key.file(),
+                        format!(
+                            "Team '{}' {} are not sorted: '{}' should come before '{}'",
+                            team_key.name,
+                            field,
+                            entries[index + 1],
+                            entries[index]
+                        ),
+                        governance.item_span(team_key, field, index + 1),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
@@ -0,0 +1,256 @@
+mod duplicates;
+mod files;
+mod github;
+mod lists;
+mod org;
+mod orphans;
+mod references;
+mod repos;
+mod slack;
+
+pub use duplicates::{DuplicateGithubUsers, DuplicateSlackChannels, DuplicateSlackMembers};
+pub use files::FileNames;
+pub use github::GithubUsers;
+pub use lists::{DuplicateEntries, UnsortedLists};
+pub use org::OrgMembers;
+pub use orphans::{OrphanContributors, OrphanRepos, TeamsWithoutChannels};
+pub use references::{DanglingMembers, DanglingRepos};
+pub use repos::{GithubRepos, UngovernedRepos};
+pub use slack::{SlackChannelMembers, SlackChannels, SlackIdentities, SlackMembers};
+
+use crate::Governance;
+use crate::config::{CONFIG_FILE, Config};
+use crate::model::{Diagnostic, Severity, SkippedCheck, Span};
+use crate::remote::Clients;
+use anyhow::{Result, bail};
+use futures::future::{BoxFuture, join_all};
+use log::info;
+use std::collections::BTreeMap;
+
+/// Metadata shared by every check, sync or async.
+pub trait CheckInfo: Send + Sync {
+    /// Stable identifier, used as the rule id in reports and on the command line.
+    fn id(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Opt-in checks only run when enabled explicitly.
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// A finding at this check's default severity.
+    fn diagnostic(&self, file: String, message: String, span: Option<Span>) -> Diagnostic {
+        Diagnostic {
+            rule: self.id().to_string(),
+            severity: self.default_severity(),
+            file,
+            message,
+            span,
+            inconclusive: false,
+        }
+    }
+
+    /// A finding that could not be confirmed either way, e.g. because a
+    /// remote lookup failed.
+    fn inconclusive(&self, file: String, message: String, span: Option<Span>) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            inconclusive: true,
+            ..self.diagnostic(file, message, span)
+        }
+    }
+}
+
+/// A check that only looks at the loaded data.
+pub trait Check: CheckInfo {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic>;
+}
+
+/// A check that needs to talk to a remote service.
+pub trait AsyncCheck: CheckInfo {
+    /// Why this check can't run right now, e.g. missing credentials.
+    fn unavailable(&self) -> Option<String> {
+        None
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>>;
+}
+
+pub struct Registry {
+    checks: Vec<Box<dyn Check>>,
+    async_checks: Vec<Box<dyn AsyncCheck>>,
+    severities: BTreeMap<String, Severity>,
+}
+
+impl Registry {
+    /// Every built-in check, including opt-in ones.
+    pub fn new(clients: &Clients, config: &Config) -> Self {
+        let org = config.github.org.clone();
+        let slack = clients.slack.clone().with_strategy(config.slack.strategy);
+        Self {
+            checks: vec![
+                Box::new(FileNames),
+                Box::new(DanglingMembers),
+                Box::new(DanglingRepos),
+                Box::new(DuplicateGithubUsers),
+                Box::new(DuplicateSlackMembers),
+                Box::new(DuplicateSlackChannels),
+                Box::new(DuplicateEntries),
+                Box::new(UnsortedLists),
+                Box::new(OrphanRepos),
+                Box::new(OrphanContributors),
+                Box::new(TeamsWithoutChannels),
+            ],
+            async_checks: vec![
+                Box::new(GithubUsers::new(clients.github.clone())),
+                Box::new(SlackMembers::new(slack.clone())),
+                Box::new(SlackChannels::new(slack.clone())),
+                Box::new(SlackIdentities::new(slack.clone())),
+                Box::new(SlackChannelMembers::new(slack)),
+                Box::new(GithubRepos::new(clients.github.clone(), org.clone())),
+                Box::new(
+                    UngovernedRepos::new(clients.github.clone(), org.clone()).with_config_file(
+                        config
+                            .file
+                            .clone()
+                            .unwrap_or_else(|| CONFIG_FILE.to_string()),
+                    ),
+                ),
+                Box::new(OrgMembers::new(clients.github.clone(), org)),
+            ],
+            severities: BTreeMap::new(),
+        }
+    }
+
+    pub fn infos(&self) -> impl Iterator<Item = &dyn CheckInfo> {
+        self.checks
+            .iter()
+            .map(|c| c.as_ref() as &dyn CheckInfo)
+            .chain(
+                self.async_checks
+                    .iter()
+                    .map(|c| c.as_ref() as &dyn CheckInfo),
+            )
+    }
+
+    fn ensure_known<'a>(&self, ids: impl IntoIterator<Item = &'a String>) -> Result<()> {
+        for id in ids {
+            if !self.infos().any(|c| c.id() == id) {
+                bail!("Unknown check: {}", id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps the checks that are enabled by default or listed in `enable`,
+    /// minus those listed in `disable`.
+    pub fn select(self, enable: &[String], disable: &[String]) -> Result<Self> {
+        self.ensure_known(enable.iter().chain(disable))?;
+
+        let selected = |c: &dyn CheckInfo| {
+            let id = c.id().to_string();
+            (c.enabled_by_default() || enable.contains(&id)) && !disable.contains(&id)
+        };
+
+        Ok(Self {
+            checks: self
+                .checks
+                .into_iter()
+                .filter(|c| selected(c.as_ref()))
+                .collect(),
+            async_checks: self
+                .async_checks
+                .into_iter()
+                .filter(|c| selected(c.as_ref()))
+                .collect(),
+            severities: self.severities,
+        })
+    }
+
+    /// Reports the given checks' findings at the given severity instead of
+    /// their default. Only findings at the check's default severity change,
+    /// and inconclusive ones never do, so a failed lookup stays a warning
+    /// even for a check whose findings are raised to errors.
+    pub fn with_severities(mut self, severities: &BTreeMap<String, Severity>) -> Result<Self> {
+        self.ensure_known(severities.keys())?;
+        self.severities.extend(severities.clone());
+        Ok(self)
+    }
+
+    /// The severity `check`'s findings are reported at.
+    pub fn severity(&self, check: &dyn CheckInfo) -> Severity {
+        self.severities
+            .get(check.id())
+            .copied()
+            .unwrap_or_else(|| check.default_severity())
+    }
+
+    fn apply_severity(
+        &self,
+        check: &dyn CheckInfo,
+        mut diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        let severity = self.severity(check);
+        for diagnostic in &mut diagnostics {
+            if !diagnostic.inconclusive && diagnostic.severity == check.default_severity() {
+                diagnostic.severity = severity;
+            }
+        }
+        diagnostics
+    }
+
+    /// Drops the remote checks that can't run, either because `offline` is
+    /// set or because their credentials are missing, and says why.
+    pub fn without_unavailable(self, offline: bool) -> (Self, Vec<SkippedCheck>) {
+        let mut skipped = Vec::new();
+        let mut async_checks = Vec::new();
+
+        for check in self.async_checks {
+            let reason = if offline {
+                "running offline".to_string()
+            } else if let Some(reason) = check.unavailable() {
+                reason
+            } else {
+                async_checks.push(check);
+                continue;
+            };
+
+            skipped.push(SkippedCheck {
+                check: check.id().to_string(),
+                reason,
+            });
+        }
+
+        (
+            Self {
+                checks: self.checks,
+                async_checks,
+                severities: self.severities,
+            },
+            skipped,
+        )
+    }
+
+    pub async fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for check in &self.checks {
+            info!("Running {}...", check.id());
+            diagnostics.extend(self.apply_severity(check.as_ref(), check.run(governance)));
+        }
+
+        let results = join_all(self.async_checks.iter().map(|check| async move {
+            info!("Running {}...", check.id());
+            self.apply_severity(check.as_ref(), check.run(governance).await)
+        }))
+        .await;
+        diagnostics.extend(results.into_iter().flatten());
+
+        diagnostics
+    }
+}
@@ -0,0 +1,102 @@
+use super::{AsyncCheck, CheckInfo};
+use crate::Governance;
+use crate::model::{Diagnostic, Severity};
+use crate::remote::{GithubClient, OrgMembership};
+use anyhow::anyhow;
+use futures::future::{BoxFuture, join_all};
+
+pub struct OrgMembers {
+    client: GithubClient,
+    org: Option<String>,
+}
+
+impl OrgMembers {
+    pub fn new(client: GithubClient, org: Option<String>) -> Self {
+        Self { client, org }
+    }
+}
+
+impl CheckInfo for OrgMembers {
+    fn id(&self) -> &'static str {
+        "org-membership"
+    }
+
+    fn description(&self) -> &'static str {
+        "Contributors should be members of the GitHub org"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+impl AsyncCheck for OrgMembers {
+    fn unavailable(&self) -> Option<String> {
+        super::repos::org_unavailable(&self.client, &self.org)
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let Some(org) = &self.org else {
+                return Vec::new();
+            };
+            let roster = self.client.org_roster(org).await;
+
+            // Ask about anyone the roster doesn't show on their own
+            let contributors = governance.contributors().collect::<Vec<_>>();
+            let memberships = join_all(contributors.iter().map(|(_, contributor)| {
+                let github = &contributor.github_username;
+                let roster = &roster;
+                async move {
+                    match roster {
+                        Ok(roster) => match roster.membership(github) {
+                            Some(membership) => Ok(membership),
+                            None => self.client.org_membership(org, github).await,
+                        },
+                        Err(e) => Err(anyhow!("{}", e)),
+                    }
+                }
+            }))
+            .await;
+
+            let mut diagnostics = Vec::new();
+            for ((contributor_id, contributor), membership) in
+                contributors.into_iter().zip(memberships)
+            {
+                let github = &contributor.github_username;
+                let file = contributor_id.file();
+                let span = governance.field_span(contributor_id, "github-username");
+                match membership {
+                    Ok(OrgMembership::Active) => {}
+                    Ok(OrgMembership::Pending) => diagnostics.push(self.diagnostic(
+                        file,
+                        format!(
+                            "GitHub user {} has not accepted the invitation to the {} org",
+                            github, org
+                        ),
+                        span,
+                    )),
+                    Ok(OrgMembership::None) => diagnostics.push(self.diagnostic(
+                        file,
+                        format!("GitHub user {} is not a member of the {} org", github, org),
+                        span,
+                    )),
+                    Err(e) => diagnostics.push(self.inconclusive(
+                        file,
+                        format!(
+                            "Failed to check {} org membership of {}: {}",
+                            org, github, e
+                        ),
+                        span,
+                    )),
+                }
+            }
+
+            diagnostics
+        })
+    }
+}
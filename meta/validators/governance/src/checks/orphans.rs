@@ -0,0 +1,106 @@
+use super::{Check, CheckInfo};
+use crate::Governance;
+use crate::model::{Diagnostic, EntityKind, Severity};
+
+pub struct OrphanRepos;
+
+impl CheckInfo for OrphanRepos {
+    fn id(&self) -> &'static str {
+        "orphan-repo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every repo should be owned by at least one team"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+impl Check for OrphanRepos {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        // A team that failed to load may be the one owning a repo
+        if governance.any_failed(EntityKind::Team) {
+            return Vec::new();
+        }
+        governance
+            .repos()
+            .filter(|(key, _)| governance.owners_of(&key.name).next().is_none())
+            .map(|(key, _)| {
+                self.diagnostic(
+                    key.file(),
+                    format!("Repo '{}' is not owned by any team", key.name),
+                    governance.field_span(key, "name"),
+                )
+            })
+            .collect()
+    }
+}
+
+pub struct OrphanContributors;
+
+impl CheckInfo for OrphanContributors {
+    fn id(&self) -> &'static str {
+        "orphan-contributor"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every contributor should be a member of at least one team"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+impl Check for OrphanContributors {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        if governance.any_failed(EntityKind::Team) {
+            return Vec::new();
+        }
+        governance
+            .contributors()
+            .filter(|(key, _)| governance.teams_of(&key.name).next().is_none())
+            .map(|(key, _)| {
+                self.diagnostic(
+                    key.file(),
+                    format!("Contributor '{}' is not a member of any team", key.name),
+                    governance.field_span(key, "github-username"),
+                )
+            })
+            .collect()
+    }
+}
+
+pub struct TeamsWithoutChannels;
+
+impl CheckInfo for TeamsWithoutChannels {
+    fn id(&self) -> &'static str {
+        "team-without-slack-channel"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every team should list at least one Slack channel"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+impl Check for TeamsWithoutChannels {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        governance
+            .teams()
+            .filter(|(_, team)| team.slack_channel_ids.is_empty())
+            .map(|(key, _)| {
+                self.diagnostic(
+                    key.file(),
+                    format!("Team '{}' has no Slack channels", key.name),
+                    governance.field_span(key, "slack-channel-ids"),
+                )
+            })
+            .collect()
+    }
+}
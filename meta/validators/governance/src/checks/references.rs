@@ -0,0 +1,76 @@
+use super::{Check, CheckInfo};
+use crate::Governance;
+use crate::model::{Diagnostic, EntityKey};
+
+pub struct DanglingMembers;
+
+impl CheckInfo for DanglingMembers {
+    fn id(&self) -> &'static str {
+        "dangling-member"
+    }
+
+    fn description(&self) -> &'static str {
+        "Team members must exist as contributors"
+    }
+}
+
+impl Check for DanglingMembers {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (team_key, team) in governance.teams() {
+            for (index, member) in team.members.iter().enumerate() {
+                let key = EntityKey::contributor(member);
+                // A contributor whose file failed to load is already reported
+                if !governance.contains(&key) && !governance.failed(&key) {
+                    diagnostics.push(self.diagnostic(
+                        team_key.file(),
+                        format!(
+                            "Team '{}' references non-existent contributor: {}",
+                            team_key.name, member
+                        ),
+                        governance.item_span(team_key, "members", index),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+pub struct DanglingRepos;
+
+impl CheckInfo for DanglingRepos {
+    fn id(&self) -> &'static str {
+        "dangling-repo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Team repos must exist as repos"
+    }
+}
+
+impl Check for DanglingRepos {
+    fn run(&self, governance: &Governance) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (team_key, team) in governance.teams() {
+            for (index, repo) in team.repos.iter().enumerate() {
+                let key = EntityKey::repo(repo);
+                if !governance.contains(&key) && !governance.failed(&key) {
+                    diagnostics.push(self.diagnostic(
+                        team_key.file(),
+                        format!(
+                            "Team '{}' references non-existent repo: {}",
+                            team_key.name, repo
+                        ),
+                        governance.item_span(team_key, "repos", index),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
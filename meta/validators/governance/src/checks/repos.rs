@@ -0,0 +1,197 @@
+use super::{AsyncCheck, CheckInfo};
+use crate::Governance;
+use crate::config::CONFIG_FILE;
+use crate::model::{Diagnostic, EntityKey, EntityKind, Severity};
+use crate::remote::GithubClient;
+use futures::future::BoxFuture;
+use std::collections::HashSet;
+
+/// Why an org check can't run, if it can't.
+pub(super) fn org_unavailable(client: &GithubClient, org: &Option<String>) -> Option<String> {
+    if org.is_none() {
+        Some("no GitHub org configured".to_string())
+    } else if !client.has_token() {
+        Some("GITHUB_TOKEN not set".to_string())
+    } else {
+        None
+    }
+}
+
+pub struct GithubRepos {
+    client: GithubClient,
+    org: Option<String>,
+}
+
+impl GithubRepos {
+    pub fn new(client: GithubClient, org: Option<String>) -> Self {
+        Self { client, org }
+    }
+}
+
+impl CheckInfo for GithubRepos {
+    fn id(&self) -> &'static str {
+        "unknown-repo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Repos must exist in the GitHub org under their governed name, and not be archived"
+    }
+}
+
+impl AsyncCheck for GithubRepos {
+    fn unavailable(&self) -> Option<String> {
+        org_unavailable(&self.client, &self.org)
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let Some(org) = &self.org else {
+                return Vec::new();
+            };
+            let mut diagnostics = Vec::new();
+
+            let repos = governance.repos().collect::<Vec<_>>();
+            let names = repos
+                .iter()
+                .map(|(_, repo)| repo.name.as_str())
+                .collect::<Vec<_>>();
+            let results = self.client.repos(org, &names).await;
+
+            for ((repo_id, repo), result) in repos.into_iter().zip(results) {
+                let name = &repo.name;
+                let file = repo_id.file();
+                let span = governance.field_span(repo_id, "name");
+                match result {
+                    Ok(Some(remote)) if !remote.name.eq_ignore_ascii_case(name) => diagnostics
+                        .push(self.diagnostic(
+                            file,
+                            format!(
+                                "Repo {}/{} has been renamed to {}/{}",
+                                org, name, org, remote.name
+                            ),
+                            span,
+                        )),
+                    Ok(Some(remote)) if remote.archived => diagnostics.push(Diagnostic {
+                        severity: Severity::Warning,
+                        ..self.diagnostic(file, format!("Repo {}/{} is archived", org, name), span)
+                    }),
+                    Ok(Some(_)) => {}
+                    Ok(None) => diagnostics.push(self.diagnostic(
+                        file,
+                        format!("Repo {}/{} does not exist", org, name),
+                        span,
+                    )),
+                    Err(e) => diagnostics.push(self.inconclusive(
+                        file,
+                        format!("Failed to check repo {}/{}: {}", org, name, e),
+                        span,
+                    )),
+                }
+            }
+
+            diagnostics
+        })
+    }
+}
+
+/// Finds repos in the org without a governance file. There is no file to
+/// report them against, so they are reported against the config file that
+/// names the org.
+pub struct UngovernedRepos {
+    client: GithubClient,
+    org: Option<String>,
+    config_file: String,
+}
+
+impl UngovernedRepos {
+    pub fn new(client: GithubClient, org: Option<String>) -> Self {
+        Self {
+            client,
+            org,
+            config_file: CONFIG_FILE.to_string(),
+        }
+    }
+
+    /// Reports against `config_file` rather than the default config file.
+    pub fn with_config_file(self, config_file: String) -> Self {
+        Self {
+            config_file,
+            ..self
+        }
+    }
+}
+
+impl CheckInfo for UngovernedRepos {
+    fn id(&self) -> &'static str {
+        "ungoverned-repo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Unarchived repos in the GitHub org should have a governance file"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+impl AsyncCheck for UngovernedRepos {
+    fn unavailable(&self) -> Option<String> {
+        org_unavailable(&self.client, &self.org)
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let Some(org) = &self.org else {
+                return Vec::new();
+            };
+
+            let remote = match self.client.org_repos(org).await {
+                Ok(remote) => remote,
+                Err(e) => {
+                    return vec![self.inconclusive(
+                        self.config_file.clone(),
+                        format!("Failed to list repos in {}: {}", org, e),
+                        None,
+                    )];
+                }
+            };
+
+            // GitHub repo names are case-insensitive. A repo whose file
+            // failed to load is governed, and already reported as broken.
+            let governed = governance
+                .repos()
+                .map(|(_, repo)| repo.name.to_lowercase())
+                .chain(
+                    governance
+                        .failed_keys(EntityKind::Repo)
+                        .map(|key| key.name.to_lowercase()),
+                )
+                .collect::<HashSet<_>>();
+
+            let mut ungoverned = remote
+                .into_iter()
+                .filter(|repo| !repo.archived && !governed.contains(&repo.name.to_lowercase()))
+                .map(|repo| repo.name)
+                .collect::<Vec<_>>();
+            ungoverned.sort();
+
+            ungoverned
+                .into_iter()
+                .map(|name| {
+                    let key = EntityKey::repo(&name);
+                    self.diagnostic(
+                        self.config_file.clone(),
+                        format!(
+                            "Repo {}/{} is not governed; add {} for it",
+                            org,
+                            name,
+                            key.file()
+                        ),
+                        None,
+                    )
+                })
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,400 @@
+use super::{AsyncCheck, CheckInfo};
+use crate::Governance;
+use crate::model::{Diagnostic, Severity};
+use crate::remote::{SlackClient, SlackProfile, SlackStatus};
+use futures::future::BoxFuture;
+use futures::{StreamExt, stream::FuturesUnordered};
+
+pub struct SlackMembers {
+    client: SlackClient,
+}
+
+impl SlackMembers {
+    pub fn new(client: SlackClient) -> Self {
+        Self { client }
+    }
+}
+
+impl CheckInfo for SlackMembers {
+    fn id(&self) -> &'static str {
+        "unknown-slack-member"
+    }
+
+    fn description(&self) -> &'static str {
+        "Contributor Slack member IDs must exist in the Slack workspace and not be deactivated"
+    }
+}
+
+impl AsyncCheck for SlackMembers {
+    fn unavailable(&self) -> Option<String> {
+        (!self.client.has_token()).then(|| "SLACK_TOKEN not set".to_string())
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let mut diagnostics = Vec::new();
+
+            let mut futures = FuturesUnordered::new();
+
+            for (contributor_id, contributor) in governance.contributors() {
+                futures.push(async move {
+                    let result = self.client.user_status(&contributor.slack_member_id).await;
+                    (contributor_id, &contributor.slack_member_id, result)
+                });
+            }
+
+            while let Some((contributor_id, slack_id, result)) = futures.next().await {
+                let file = contributor_id.file();
+                let span = governance.field_span(contributor_id, "slack-member-id");
+                match result {
+                    Ok(SlackStatus::Active) => {}
+                    Ok(SlackStatus::Inactive) => diagnostics.push(Diagnostic {
+                        severity: Severity::Warning,
+                        ..self.diagnostic(
+                            file,
+                            format!("Slack member {} is deactivated", slack_id),
+                            span,
+                        )
+                    }),
+                    Ok(SlackStatus::Missing) => diagnostics.push(self.diagnostic(
+                        file,
+                        format!("Slack member ID does not exist: {}", slack_id),
+                        span,
+                    )),
+                    Err(e) => diagnostics.push(self.inconclusive(
+                        file,
+                        format!("Failed to check Slack member ID {}: {}", slack_id, e),
+                        span,
+                    )),
+                }
+            }
+
+            diagnostics
+        })
+    }
+}
+
+pub struct SlackChannels {
+    client: SlackClient,
+}
+
+impl SlackChannels {
+    pub fn new(client: SlackClient) -> Self {
+        Self { client }
+    }
+}
+
+impl CheckInfo for SlackChannels {
+    fn id(&self) -> &'static str {
+        "unknown-slack-channel"
+    }
+
+    fn description(&self) -> &'static str {
+        "Team Slack channel IDs must exist in the Slack workspace and not be archived"
+    }
+}
+
+impl AsyncCheck for SlackChannels {
+    fn unavailable(&self) -> Option<String> {
+        (!self.client.has_token()).then(|| "SLACK_TOKEN not set".to_string())
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let mut diagnostics = Vec::new();
+
+            let mut futures = FuturesUnordered::new();
+
+            for (team_id, team) in governance.teams() {
+                for (index, channel_id) in team.slack_channel_ids.iter().enumerate() {
+                    futures.push(async move {
+                        let result = self.client.channel_status(channel_id).await;
+                        (team_id, index, channel_id, result)
+                    });
+                }
+            }
+
+            while let Some((team_id, index, channel_id, result)) = futures.next().await {
+                let file = team_id.file();
+                let span = governance.item_span(team_id, "slack-channel-ids", index);
+                match result {
+                    Ok(SlackStatus::Active) => {}
+                    Ok(SlackStatus::Inactive) => diagnostics.push(Diagnostic {
+                        severity: Severity::Warning,
+                        ..self.diagnostic(
+                            file,
+                            format!("Slack channel {} is archived", channel_id),
+                            span,
+                        )
+                    }),
+                    Ok(SlackStatus::Missing) => diagnostics.push(self.diagnostic(
+                        file,
+                        format!("Slack channel ID does not exist: {}", channel_id),
+                        span,
+                    )),
+                    Err(e) => diagnostics.push(self.inconclusive(
+                        file,
+                        format!("Failed to check Slack channel ID {}: {}", channel_id, e),
+                        span,
+                    )),
+                }
+            }
+
+            diagnostics
+        })
+    }
+}
+
+pub struct SlackIdentities {
+    client: SlackClient,
+}
+
+impl SlackIdentities {
+    pub fn new(client: SlackClient) -> Self {
+        Self { client }
+    }
+}
+
+impl CheckInfo for SlackIdentities {
+    fn id(&self) -> &'static str {
+        "slack-identity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Contributor full names should match their Slack profile, which should be a person's account"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+impl AsyncCheck for SlackIdentities {
+    fn unavailable(&self) -> Option<String> {
+        (!self.client.has_token()).then(|| "SLACK_TOKEN not set".to_string())
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let mut diagnostics = Vec::new();
+
+            let mut futures = FuturesUnordered::new();
+
+            for (contributor_id, contributor) in governance.contributors() {
+                futures.push(async move {
+                    let result = self.client.user_profile(&contributor.slack_member_id).await;
+                    (contributor_id, contributor, result)
+                });
+            }
+
+            while let Some((contributor_id, contributor, result)) = futures.next().await {
+                let slack_id = &contributor.slack_member_id;
+                let file = contributor_id.file();
+                let span = governance.field_span(contributor_id, "slack-member-id");
+                let profile = match result {
+                    // Missing and deactivated members are reported by
+                    // unknown-slack-member
+                    Ok(Some(profile)) if profile.deleted => continue,
+                    Ok(Some(profile)) => profile,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        diagnostics.push(self.inconclusive(
+                            file,
+                            format!("Failed to check Slack profile of {}: {}", slack_id, e),
+                            span,
+                        ));
+                        continue;
+                    }
+                };
+
+                let observed = observed_name(&profile);
+                let named = !profile.real_name.is_empty() || !profile.display_name.is_empty();
+                let matches = names_match(&contributor.full_name, &profile.real_name)
+                    || names_match(&contributor.full_name, &profile.display_name);
+                if profile.bot {
+                    diagnostics.push(self.diagnostic(
+                        file,
+                        format!("Slack member {} is a bot account ({})", slack_id, observed),
+                        span,
+                    ));
+                } else if named && !matches {
+                    diagnostics.push(self.diagnostic(
+                        file,
+                        format!(
+                            "Full name '{}' doesn't match the Slack profile of {} ({})",
+                            contributor.full_name, slack_id, observed
+                        ),
+                        span,
+                    ));
+                }
+            }
+
+            diagnostics
+        })
+    }
+}
+
+/// The names on a Slack profile, for reviewers to compare by eye.
+fn observed_name(profile: &SlackProfile) -> String {
+    match (profile.real_name.as_str(), profile.display_name.as_str()) {
+        ("", "") => "no name set".to_string(),
+        (name, "") | ("", name) => format!("'{}'", name),
+        (real, display) if real == display => format!("'{}'", real),
+        (real, display) => format!("'{}', shown as '{}'", real, display),
+    }
+}
+
+/// The lowercase words of `name`, ignoring punctuation.
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether two words could name the same thing: equal, an initial of the
+/// other, or a shortening of at least three letters ("alex", "alexander").
+fn same_word(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let length = short.chars().count();
+    long.starts_with(short) && (length == 1 || length >= 3 || short == long)
+}
+
+/// Whether `full_name` plausibly belongs to whoever uses `slack_name`. Every
+/// word of the shorter name has to match a word of the longer one, with at
+/// least one more than an initial; failing that, names written without
+/// spaces ("alicesmith") may differ by the odd typo.
+fn names_match(full_name: &str, slack_name: &str) -> bool {
+    let (a, b) = (words(full_name), words(slack_name));
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+
+    let (short, long) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    if short
+        .iter()
+        .all(|word| long.iter().any(|other| same_word(word, other)))
+        && short.iter().any(|word| word.chars().count() > 1)
+    {
+        return true;
+    }
+
+    let (a, b) = (a.concat(), b.concat());
+    edit_distance(&a, &b) <= a.chars().count().max(b.chars().count()) / 8
+}
+
+/// Levenshtein distance between `a` and `b`, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+pub struct SlackChannelMembers {
+    client: SlackClient,
+}
+
+impl SlackChannelMembers {
+    pub fn new(client: SlackClient) -> Self {
+        Self { client }
+    }
+}
+
+impl CheckInfo for SlackChannelMembers {
+    fn id(&self) -> &'static str {
+        "slack-channel-membership"
+    }
+
+    fn description(&self) -> &'static str {
+        "Team members should be in each of the team's Slack channels"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+impl AsyncCheck for SlackChannelMembers {
+    fn unavailable(&self) -> Option<String> {
+        (!self.client.has_token()).then(|| "SLACK_TOKEN not set".to_string())
+    }
+
+    fn run<'a>(&'a self, governance: &'a Governance) -> BoxFuture<'a, Vec<Diagnostic>> {
+        Box::pin(async move {
+            let mut diagnostics = Vec::new();
+
+            let mut futures = FuturesUnordered::new();
+
+            for (team_id, team) in governance.teams() {
+                for (index, channel_id) in team.slack_channel_ids.iter().enumerate() {
+                    futures.push(async move {
+                        let result = self.client.channel_members(channel_id).await;
+                        (team_id, team, index, channel_id, result)
+                    });
+                }
+            }
+
+            while let Some((team_id, team, index, channel_id, result)) = futures.next().await {
+                let file = team_id.file();
+                let channel_members = match result {
+                    Ok(Some(channel_members)) => channel_members,
+                    // Missing channels are reported by unknown-slack-channel
+                    Ok(None) => continue,
+                    Err(e) => {
+                        diagnostics.push(self.inconclusive(
+                            file,
+                            format!(
+                                "Failed to list members of Slack channel {}: {}",
+                                channel_id, e
+                            ),
+                            governance.item_span(team_id, "slack-channel-ids", index),
+                        ));
+                        continue;
+                    }
+                };
+
+                for (member_index, member) in team.members.iter().enumerate() {
+                    // Unknown members are reported by dangling-member
+                    let Some(contributor) = governance.contributor(member) else {
+                        continue;
+                    };
+                    if channel_members.contains(contributor.slack_member_id.as_str()) {
+                        continue;
+                    }
+                    diagnostics.push(self.diagnostic(
+                        file.clone(),
+                        format!(
+                            "Team '{}' member '{}' is not in Slack channel {}",
+                            team.name, member, channel_id
+                        ),
+                        governance.item_span(team_id, "members", member_index),
+                    ));
+                }
+            }
+
+            diagnostics
+        })
+    }
+}
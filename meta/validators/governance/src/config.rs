@@ -0,0 +1,116 @@
+use crate::model::Severity;
+use crate::remote::{DEFAULT_CACHE_FILE, DEFAULT_CACHE_TTL, SlackStrategy};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the optional validator configuration file in the governance root.
+pub const CONFIG_FILE: &str = "validator.toml";
+
+/// Validator settings, read from [`CONFIG_FILE`]. Every section is optional.
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub checks: ChecksConfig,
+
+    #[serde(default)]
+    pub github: GithubConfig,
+
+    #[serde(default)]
+    pub slack: SlackConfig,
+
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    /// Where this config was read from, relative to the governance root if
+    /// it is inside it. `None` for the default config.
+    #[serde(skip)]
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ChecksConfig {
+    /// Opt-in checks to run, in addition to those given with `--enable`.
+    #[serde(default)]
+    pub enable: Vec<String>,
+
+    /// Checks to skip, in addition to those given with `--disable`.
+    #[serde(default)]
+    pub disable: Vec<String>,
+
+    /// Severity overrides, keyed by check id.
+    #[serde(default)]
+    pub severity: BTreeMap<String, Severity>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct GithubConfig {
+    /// The organization governed repos live in. Org checks are skipped
+    /// without it.
+    pub org: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct SlackConfig {
+    /// Whether to look IDs up one at a time or list the whole workspace.
+    #[serde(default)]
+    pub strategy: SlackStrategy,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, default)]
+pub struct CacheConfig {
+    /// Whether to cache remote lookups on disk, as with `--cache`.
+    pub enable: bool,
+
+    /// The cache file, relative to the governance root.
+    pub path: PathBuf,
+
+    /// Hours a confirmed lookup is trusted before it is checked again.
+    pub ttl_hours: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            path: PathBuf::from(DEFAULT_CACHE_FILE),
+            ttl_hours: DEFAULT_CACHE_TTL.as_secs() / 3600,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config at `path`, or `root/validator.toml` if no path is
+    /// given. A missing default file means the default config; an explicitly
+    /// given one must exist.
+    pub fn load(root: &Path, path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let path = root.join(CONFIG_FILE);
+                if !path.is_file() {
+                    return Ok(Self::default());
+                }
+                path
+            }
+        };
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        let file = path.strip_prefix(root).unwrap_or(&path);
+        Ok(Self {
+            file: Some(file.to_string_lossy().replace('\\', "/")),
+            ..config
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use crate::Governance;
+use crate::model::{EntityKey, EntityKind, Team};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Result};
+
+/// How something differs from one revision to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Change {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.pad(match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Modified => "modified",
+        })
+    }
+}
+
+/// An entity whose file differs between the revisions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EntityChange {
+    pub key: EntityKey,
+    pub change: Change,
+}
+
+/// A name added to or removed from one of a team's lists.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ListChange {
+    pub team: EntityKey,
+    pub name: String,
+    pub change: Change,
+}
+
+/// What changed in the governance data between two revisions, e.g. the base
+/// and head of a pull request. Everything is in key order.
+#[derive(Debug, Default)]
+pub struct GovernanceDiff {
+    pub entities: Vec<EntityChange>,
+    /// Contributors joining or leaving teams, including by the team itself
+    /// being added or removed.
+    pub members: Vec<ListChange>,
+    /// Repos teams take on or give up, likewise.
+    pub repos: Vec<ListChange>,
+}
+
+impl GovernanceDiff {
+    /// Compares `head` against `base`. A file that fails to load on one side
+    /// counts as modified rather than added or removed, and its lists are
+    /// left out, since what it says there is unknown.
+    pub fn between(base: &Governance, head: &Governance) -> Self {
+        let keys = base
+            .entities()
+            .chain(head.entities())
+            .map(|(key, _)| key)
+            .collect::<BTreeSet<_>>();
+
+        let entities = keys
+            .iter()
+            .filter_map(|&key| {
+                let change = match (base.get(key), head.get(key)) {
+                    (Some(before), Some(after)) if before == after => return None,
+                    (Some(_), Some(_)) => Change::Modified,
+                    (Some(_), None) if head.failed(key) => Change::Modified,
+                    (None, Some(_)) if base.failed(key) => Change::Modified,
+                    (Some(_), None) => Change::Removed,
+                    (None, Some(_)) => Change::Added,
+                    (None, None) => return None,
+                };
+                Some(EntityChange {
+                    key: key.clone(),
+                    change,
+                })
+            })
+            .collect();
+
+        let teams = keys
+            .into_iter()
+            .filter(|key| key.kind == EntityKind::Team && !base.failed(key) && !head.failed(key))
+            .collect::<Vec<_>>();
+        let lists = |list: fn(&Team) -> &Vec<String>| {
+            let mut changes = teams
+                .iter()
+                .flat_map(|key| {
+                    let [before, after] = [base, head].map(|governance| {
+                        governance
+                            .team(&key.name)
+                            .map(|team| list(team).iter().collect::<BTreeSet<_>>())
+                            .unwrap_or_default()
+                    });
+                    let removed = before
+                        .difference(&after)
+                        .map(|name| (*name, Change::Removed));
+                    let added = after.difference(&before).map(|name| (*name, Change::Added));
+                    removed
+                        .chain(added)
+                        .map(|(name, change)| ListChange {
+                            team: (*key).clone(),
+                            name: name.clone(),
+                            change,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            changes.sort();
+            changes
+        };
+
+        Self {
+            entities,
+            members: lists(|team| &team.members),
+            repos: lists(|team| &team.repos),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.members.is_empty() && self.repos.is_empty()
+    }
+
+    /// The entities with the given `change`.
+    pub fn with(&self, change: Change) -> impl Iterator<Item = &EntityKey> {
+        self.entities
+            .iter()
+            .filter(move |entity| entity.change == change)
+            .map(|entity| &entity.key)
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::{Context, Result, bail};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Every file under `dirs` at revision `rev` of the git repository holding
+/// `root`, as `(path, content)` pairs with paths relative to `root`. The
+/// blobs are read from the object database, so the working tree is left
+/// alone and may be at any revision.
+pub fn read_files(root: &Path, rev: &str, dirs: &[&str]) -> Result<Vec<(String, Vec<u8>)>> {
+    // Keep a revision like `--output=x` from being taken as an option
+    if rev.starts_with('-') {
+        bail!("Invalid git revision: {}", rev);
+    }
+
+    let listing = git(root, &[&["ls-tree", "-r", "-z", rev, "--"], dirs].concat())?;
+    let blobs = String::from_utf8(listing.stdout)
+        .context("git ls-tree listed a path that isn't UTF-8")?
+        .split_terminator('\0')
+        .filter_map(|entry| {
+            // <mode> SP <type> SP <object> TAB <path>
+            let (info, path) = entry.split_once('\t')?;
+            let mut info = info.split(' ');
+            let (_, kind, object) = (info.next()?, info.next()?, info.next()?);
+            (kind == "blob").then(|| (path.to_string(), object.to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    let objects = blobs
+        .iter()
+        .map(|(_, object)| format!("{}\n", object))
+        .collect::<String>();
+    let contents = cat_blobs(root, objects)?;
+    if contents.len() != blobs.len() {
+        bail!(
+            "git cat-file returned {} of {} blobs",
+            contents.len(),
+            blobs.len()
+        );
+    }
+
+    Ok(blobs
+        .into_iter()
+        .map(|(path, _)| path)
+        .zip(contents)
+        .collect())
+}
+
+/// The contents of the newline-separated `objects`, in order, read through
+/// one `git cat-file --batch`.
+fn cat_blobs(root: &Path, objects: String) -> Result<Vec<Vec<u8>>> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["cat-file", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run git")?;
+
+    // Write from another thread, so that a full stdout pipe can't leave git
+    // and us each waiting on the other
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = std::thread::spawn(move || stdin.write_all(objects.as_bytes()));
+    let output = child.wait_with_output().context("Failed to run git")?;
+    writer
+        .join()
+        .expect("writing to git panicked")
+        .context("Failed to write to git cat-file")?;
+    let output = check("cat-file", output)?;
+
+    // Each object is `<object> SP <type> SP <size> LF <content> LF`
+    let mut contents = Vec::new();
+    let mut rest = &output.stdout[..];
+    while let Some(end) = rest.iter().position(|&b| b == b'\n') {
+        let header = String::from_utf8_lossy(&rest[..end]);
+        let size = match header.split(' ').collect::<Vec<_>>()[..] {
+            [_, "blob", size] => size.parse::<usize>().ok(),
+            _ => None,
+        };
+        let Some(size) = size.filter(|size| end + 1 + size < rest.len()) else {
+            bail!("Unexpected output from git cat-file: {}", header);
+        };
+        contents.push(rest[end + 1..end + 1 + size].to_vec());
+        rest = &rest[end + 1 + size + 1..];
+    }
+    Ok(contents)
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<Output> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    check(args[0], output)
+}
+
+fn check(command: &str, output: Output) -> Result<Output> {
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output)
+}
@@ -0,0 +1,239 @@
+use crate::git;
+use crate::loader::{Loaded, load_contributors, load_from_files, load_repos, load_teams};
+use crate::model::{
+    Contributor, Entity, EntityKey, EntityKind, Repo, SourceSpans, Span, Team, ValidationError,
+};
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+/// All governance data loaded from a root directory, indexed by
+/// [`EntityKey`] across every kind.
+#[derive(Debug, Default)]
+pub struct Governance {
+    entities: HashMap<EntityKey, Entity>,
+    spans: HashMap<EntityKey, SourceSpans>,
+    errors: Vec<ValidationError>,
+    /// Keys of the files in `errors`, whose entities are unknown.
+    failed: HashSet<EntityKey>,
+    /// Contributor key to the teams listing them as a member.
+    teams_by_member: HashMap<EntityKey, BTreeSet<EntityKey>>,
+    /// Repo key to the teams listing it among their repos.
+    teams_by_repo: HashMap<EntityKey, BTreeSet<EntityKey>>,
+}
+
+impl Governance {
+    pub fn load(root: &Path) -> Result<Self> {
+        let mut governance = Self::default();
+        governance.extend(load_contributors(root)?);
+        governance.extend(load_teams(root)?);
+        governance.extend(load_repos(root)?);
+        governance.index();
+        Ok(governance)
+    }
+
+    /// The governance data as of git revision `rev`, read from the history
+    /// of the repository holding `root` without checking it out.
+    pub fn load_revision(root: &Path, rev: &str) -> Result<Self> {
+        let dirs = [EntityKind::Contributor, EntityKind::Team, EntityKind::Repo].map(|k| k.dir());
+        let files = git::read_files(root, rev, &dirs)
+            .with_context(|| format!("Failed to read the governance data at {}", rev))?;
+        Ok(Self::from_files(&files))
+    }
+
+    /// Governance data from in-memory `(path, content)` files, with paths
+    /// relative to the governance root.
+    pub fn from_files(files: &[(String, Vec<u8>)]) -> Self {
+        let mut governance = Self::default();
+        governance.extend(load_from_files::<Contributor>(
+            files,
+            EntityKind::Contributor,
+        ));
+        governance.extend(load_from_files::<Team>(files, EntityKind::Team));
+        governance.extend(load_from_files::<Repo>(files, EntityKind::Repo));
+        governance.index();
+        governance
+    }
+
+    fn extend<T: Into<Entity>>(&mut self, loaded: Loaded<T>) {
+        self.entities
+            .extend(loaded.items.into_iter().map(|(k, v)| (k, v.into())));
+        self.spans.extend(loaded.spans);
+        self.failed.extend(
+            loaded
+                .errors
+                .iter()
+                .filter_map(|error| failed_key(&error.file)),
+        );
+        self.errors.extend(loaded.errors);
+    }
+
+    /// Rebuilds the reverse indexes from each team's `members` and `repos`.
+    /// References are indexed as written, whether or not the entity they
+    /// name was loaded.
+    fn index(&mut self) {
+        let mut teams_by_member: HashMap<EntityKey, BTreeSet<EntityKey>> = HashMap::new();
+        let mut teams_by_repo: HashMap<EntityKey, BTreeSet<EntityKey>> = HashMap::new();
+
+        for (key, team) in self.teams() {
+            for member in &team.members {
+                teams_by_member
+                    .entry(EntityKey::contributor(member))
+                    .or_default()
+                    .insert(key.clone());
+            }
+            for repo in &team.repos {
+                teams_by_repo
+                    .entry(EntityKey::repo(repo))
+                    .or_default()
+                    .insert(key.clone());
+            }
+        }
+
+        self.teams_by_member = teams_by_member;
+        self.teams_by_repo = teams_by_repo;
+    }
+
+    /// Every file that failed to load, across all three data directories.
+    pub fn load_errors(&self) -> impl Iterator<Item = &ValidationError> {
+        self.errors.iter()
+    }
+
+    /// Whether the file for `key` exists but failed to load, so that what it
+    /// says is unknown. Checks skip references to such entities rather than
+    /// reporting them missing on top of the load error.
+    pub fn failed(&self, key: &EntityKey) -> bool {
+        self.failed.contains(key)
+    }
+
+    /// Whether any file of `kind` failed to load.
+    pub fn any_failed(&self, kind: EntityKind) -> bool {
+        self.failed_keys(kind).next().is_some()
+    }
+
+    /// The keys of the files of `kind` that failed to load, in no order.
+    pub fn failed_keys(&self, kind: EntityKind) -> impl Iterator<Item = &EntityKey> {
+        self.failed.iter().filter(move |key| key.kind == kind)
+    }
+
+    pub fn get(&self, key: &EntityKey) -> Option<&Entity> {
+        self.entities.get(key)
+    }
+
+    pub fn contains(&self, key: &EntityKey) -> bool {
+        self.entities.contains_key(key)
+    }
+
+    /// Every loaded entity, in no particular order.
+    pub fn entities(&self) -> impl Iterator<Item = (&EntityKey, &Entity)> {
+        self.entities.iter()
+    }
+
+    /// The number of loaded entities of `kind`.
+    pub fn count(&self, kind: EntityKind) -> usize {
+        self.entities.keys().filter(|k| k.kind == kind).count()
+    }
+
+    pub fn contributor(&self, name: &str) -> Option<&Contributor> {
+        match self.get(&EntityKey::contributor(name))? {
+            Entity::Contributor(contributor) => Some(contributor),
+            _ => None,
+        }
+    }
+
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        match self.get(&EntityKey::team(name))? {
+            Entity::Team(team) => Some(team),
+            _ => None,
+        }
+    }
+
+    pub fn repo(&self, name: &str) -> Option<&Repo> {
+        match self.get(&EntityKey::repo(name))? {
+            Entity::Repo(repo) => Some(repo),
+            _ => None,
+        }
+    }
+
+    pub fn contributors(&self) -> impl Iterator<Item = (&EntityKey, &Contributor)> {
+        self.entities
+            .iter()
+            .filter_map(|(key, entity)| match entity {
+                Entity::Contributor(contributor) => Some((key, contributor)),
+                _ => None,
+            })
+    }
+
+    pub fn teams(&self) -> impl Iterator<Item = (&EntityKey, &Team)> {
+        self.entities
+            .iter()
+            .filter_map(|(key, entity)| match entity {
+                Entity::Team(team) => Some((key, team)),
+                _ => None,
+            })
+    }
+
+    pub fn repos(&self) -> impl Iterator<Item = (&EntityKey, &Repo)> {
+        self.entities
+            .iter()
+            .filter_map(|(key, entity)| match entity {
+                Entity::Repo(repo) => Some((key, repo)),
+                _ => None,
+            })
+    }
+
+    /// Location of the top-level `field` in the file for `key`.
+    pub fn field_span(&self, key: &EntityKey, field: &str) -> Option<Span> {
+        self.spans.get(key).and_then(|spans| spans.field(field))
+    }
+
+    /// Location of the `index`th item of the array `field` in the file for `key`.
+    pub fn item_span(&self, key: &EntityKey, field: &str, index: usize) -> Option<Span> {
+        self.spans
+            .get(key)
+            .and_then(|spans| spans.item(field, index))
+    }
+
+    /// Teams listing `contributor` as a member, in name order.
+    pub fn teams_of(&self, contributor: &str) -> impl Iterator<Item = &EntityKey> {
+        self.teams_by_member
+            .get(&EntityKey::contributor(contributor))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Teams listing `repo` among their repos, in name order.
+    pub fn owners_of(&self, repo: &str) -> impl Iterator<Item = &EntityKey> {
+        self.teams_by_repo
+            .get(&EntityKey::repo(repo))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Repos `contributor` works on through any of their teams.
+    pub fn repos_of(&self, contributor: &str) -> BTreeSet<EntityKey> {
+        self.teams_of(contributor)
+            .filter_map(|team| self.team(&team.name))
+            .flat_map(|team| &team.repos)
+            .map(EntityKey::repo)
+            .collect()
+    }
+
+    /// Contributors working on `repo` through any of the teams owning it.
+    pub fn members_of(&self, repo: &str) -> BTreeSet<EntityKey> {
+        self.owners_of(repo)
+            .filter_map(|team| self.team(&team.name))
+            .flat_map(|team| &team.members)
+            .map(EntityKey::contributor)
+            .collect()
+    }
+}
+
+/// The key of the entity defined by `file`, a path relative to the root.
+fn failed_key(file: &str) -> Option<EntityKey> {
+    let (dir, name) = file.split_once('/')?;
+    let kind = [EntityKind::Contributor, EntityKind::Team, EntityKind::Repo]
+        .into_iter()
+        .find(|kind| kind.dir() == dir)?;
+    Some(EntityKey::new(kind, name.strip_suffix(".toml")?))
+}
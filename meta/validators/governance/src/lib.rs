@@ -1,2 +1,13 @@
+pub mod checks;
+pub mod config;
+pub mod diff;
+mod git;
+mod governance;
 pub mod loader;
 pub mod model;
+pub mod policy;
+pub mod remote;
+pub mod render;
+pub mod rules;
+
+pub use governance::Governance;
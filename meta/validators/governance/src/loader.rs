@@ -1,45 +1,333 @@
-use crate::model::{Contributor, EntityKey, Repo, Team};
-use anyhow::{Context, Result};
-use glob::glob;
+use crate::model::{
+    Contributor, EntityKey, EntityKind, Position, Repo, SourceSpans, Span, Team, ValidationError,
+};
+use crate::rules;
+use anyhow::{Context, Result, bail};
+use glob::{Pattern, glob};
 use serde::de::DeserializeOwned;
-use std::fmt::Debug;
-use std::{collections::HashMap, fs};
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::{collections::HashMap, env, fs};
+use toml_edit::{ImDocument, Item, Table, Value};
 
-const CONTRIBUTORS_PATH: &str = "contributors/*.toml";
-const TEAMS_PATH: &str = "teams/*.toml";
-const REPOS_PATH: &str = "repos/*.toml";
+pub const CONTRIBUTORS_DIR: &str = EntityKind::Contributor.dir();
+pub const TEAMS_DIR: &str = EntityKind::Team.dir();
+pub const REPOS_DIR: &str = EntityKind::Repo.dir();
+
+/// Walks up from `start` until a directory containing all of the governance
+/// data directories is found.
+pub fn find_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| is_root(dir))
+        .map(Path::to_path_buf)
+}
+
+fn is_root(dir: &Path) -> bool {
+    [CONTRIBUTORS_DIR, TEAMS_DIR, REPOS_DIR]
+        .iter()
+        .all(|sub| dir.join(sub).is_dir())
+}
+
+/// Uses `root` if one was given explicitly, otherwise discovers the
+/// governance root from the current working directory.
+pub fn resolve_root(root: Option<&Path>) -> Result<PathBuf> {
+    match root {
+        Some(root) => {
+            if !is_root(root) {
+                bail!(
+                    "{} is not a governance root (expected {}/, {}/ and {}/ directories)",
+                    root.display(),
+                    CONTRIBUTORS_DIR,
+                    TEAMS_DIR,
+                    REPOS_DIR
+                );
+            }
+            Ok(root.to_path_buf())
+        }
+        None => {
+            let cwd = env::current_dir().context("Failed to determine current directory")?;
+            find_root(&cwd).with_context(|| {
+                format!(
+                    "Could not find the governance root from {}; pass --root explicitly",
+                    cwd.display()
+                )
+            })
+        }
+    }
+}
+
+/// Entities loaded from one data directory, alongside every file that could
+/// not be read or deserialized.
+#[derive(Debug)]
+pub struct Loaded<T> {
+    pub items: HashMap<EntityKey, T>,
+    pub spans: HashMap<EntityKey, SourceSpans>,
+    pub errors: Vec<ValidationError>,
+}
+
+impl<T> Loaded<T> {
+    /// Location of the top-level `field` in the file for `key`.
+    pub fn field_span(&self, key: &EntityKey, field: &str) -> Option<Span> {
+        self.spans.get(key).and_then(|spans| spans.field(field))
+    }
+
+    /// Location of the `index`th item of the array `field` in the file for `key`.
+    pub fn item_span(&self, key: &EntityKey, field: &str, index: usize) -> Option<Span> {
+        self.spans
+            .get(key)
+            .and_then(|spans| spans.item(field, index))
+    }
+}
 
 pub fn load_from_dir<T: DeserializeOwned + Debug>(
-    path_glob: &str,
-    item_name: &str,
-) -> Result<HashMap<EntityKey, T>> {
-    let mut map = HashMap::new();
-    for entry in glob(path_glob)? {
-        let path = entry?;
+    root: &Path,
+    kind: EntityKind,
+) -> Result<Loaded<T>> {
+    let path_glob = format!(
+        "{}/*.toml",
+        Pattern::escape(&root.join(kind.dir()).to_string_lossy())
+    );
+
+    let mut items = HashMap::new();
+    let mut spans = HashMap::new();
+    let mut errors = Vec::new();
+    for entry in glob(&path_glob)? {
+        let path = match entry {
+            Ok(path) => path,
+            Err(e) => {
+                errors.push(read_error(&relative_path(root, e.path()), kind, e.error()));
+                continue;
+            }
+        };
         let file_stem = path.file_stem().unwrap().to_string_lossy().to_string();
-        let content = fs::read_to_string(&path)
-            .with_context(|| format!("Failed to read {} file: {}", item_name, path.display()))?;
-        let item: T = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse {} file: {}", item_name, path.display()))?;
-
-        let key = EntityKey {
-            kind: item_name.to_string(),
-            name: file_stem,
+        match load_file(root, &path, kind) {
+            Ok((item, file_spans)) => {
+                let key = EntityKey::new(kind, file_stem);
+                spans.insert(key.clone(), file_spans);
+                items.insert(key, item);
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+
+    Ok(Loaded {
+        items,
+        spans,
+        errors,
+    })
+}
+
+/// Reads and deserializes the `kind` file at `path`, recording where each of
+/// its values is. Errors name the file relative to `root`.
+pub fn load_file<T: DeserializeOwned>(
+    root: &Path,
+    path: &Path,
+    kind: EntityKind,
+) -> std::result::Result<(T, SourceSpans), ValidationError> {
+    let file = relative_path(root, path);
+
+    let content = fs::read_to_string(path).map_err(|e| read_error(&file, kind, e))?;
+    parse_file(file, &content, kind)
+}
+
+/// Loads the `kind` files among in-memory `(path, content)` pairs, e.g. as
+/// read from a git revision. Paths are relative to the governance root; only
+/// TOML files directly in `kind`'s data directory are loaded.
+pub fn load_from_files<T: DeserializeOwned + Debug>(
+    files: &[(String, Vec<u8>)],
+    kind: EntityKind,
+) -> Loaded<T> {
+    let mut items = HashMap::new();
+    let mut spans = HashMap::new();
+    let mut errors = Vec::new();
+    for (file, content) in files {
+        let Some(file_stem) = file
+            .strip_prefix(kind.dir())
+            .and_then(|rest| rest.strip_prefix('/'))
+            .and_then(|name| name.strip_suffix(".toml"))
+            .filter(|stem| !stem.contains('/'))
+        else {
+            continue;
         };
-        map.insert(key, item);
+
+        let loaded = std::str::from_utf8(content)
+            .map_err(|e| read_error(file, kind, e))
+            .and_then(|content| parse_file(file.clone(), content, kind));
+        match loaded {
+            Ok((item, file_spans)) => {
+                let key = EntityKey::new(kind, file_stem);
+                spans.insert(key.clone(), file_spans);
+                items.insert(key, item);
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+
+    Loaded {
+        items,
+        spans,
+        errors,
     }
+}
+
+/// Deserializes the `content` of the `kind` file named `file`, recording
+/// where each of its values is.
+pub fn parse_file<T: DeserializeOwned>(
+    file: String,
+    content: &str,
+    kind: EntityKind,
+) -> std::result::Result<(T, SourceSpans), ValidationError> {
+    let lines = LineIndex::new(content);
+    let item = toml::from_str(content).map_err(|e| ValidationError {
+        rule: rules::INVALID_FILE.id.to_string(),
+        file,
+        message: format!(
+            "Failed to parse {} file: {}",
+            kind,
+            e.message().trim_end().replace('\n', ", ")
+        ),
+        span: e.span().map(|range| lines.span(range)),
+    })?;
+
+    Ok((item, collect_spans(content, &lines)))
+}
+
+fn read_error(file: &str, kind: EntityKind, error: impl Display) -> ValidationError {
+    ValidationError {
+        rule: rules::INVALID_FILE.id.to_string(),
+        file: file.to_string(),
+        message: format!("Failed to read {} file: {}", kind, error),
+        span: None,
+    }
+}
+
+/// Byte offsets of the start of each line, for turning byte ranges into
+/// line/column positions.
+struct LineIndex<'a> {
+    content: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(content: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            content,
+            line_starts,
+        }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.content.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let column = self.content[line_start..offset].chars().count();
+        Position {
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+
+    fn span(&self, range: Range<usize>) -> Span {
+        Span {
+            start: self.position(range.start),
+            end: self.position(range.end),
+        }
+    }
+}
+
+/// Records the span of every value in the document, keyed by its TOML path.
+/// The content has already deserialized successfully, so a parse failure
+/// here just means no spans are available.
+fn collect_spans(content: &str, lines: &LineIndex) -> SourceSpans {
+    let mut spans = SourceSpans::default();
+    if let Ok(document) = ImDocument::parse(content) {
+        collect_table(document.as_table(), "", lines, &mut spans);
+    }
+    spans
+}
+
+fn collect_table(table: &Table, prefix: &str, lines: &LineIndex, spans: &mut SourceSpans) {
+    for (key, item) in table.iter() {
+        let path = join_path(prefix, key);
+        match item {
+            Item::Value(value) => collect_value(value, path, lines, spans),
+            Item::Table(table) => {
+                insert_span(table.span(), &path, lines, spans);
+                collect_table(table, &path, lines, spans);
+            }
+            Item::ArrayOfTables(array) => {
+                insert_span(array.span(), &path, lines, spans);
+                for (index, table) in array.iter().enumerate() {
+                    let path = format!("{}[{}]", path, index);
+                    insert_span(table.span(), &path, lines, spans);
+                    collect_table(table, &path, lines, spans);
+                }
+            }
+            Item::None => {}
+        }
+    }
+}
+
+fn collect_value(value: &Value, path: String, lines: &LineIndex, spans: &mut SourceSpans) {
+    insert_span(value.span(), &path, lines, spans);
+    match value {
+        Value::Array(array) => {
+            for (index, item) in array.iter().enumerate() {
+                collect_value(item, format!("{}[{}]", path, index), lines, spans);
+            }
+        }
+        Value::InlineTable(table) => {
+            for (key, item) in table.iter() {
+                collect_value(item, join_path(&path, key), lines, spans);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn insert_span(
+    range: Option<Range<usize>>,
+    path: &str,
+    lines: &LineIndex,
+    spans: &mut SourceSpans,
+) {
+    if let Some(range) = range {
+        spans.spans.insert(path.to_string(), lines.span(range));
+    }
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
 
-    Ok(map)
+/// Renders `path` relative to the governance root with forward slashes, the
+/// form used for file names throughout the validation report.
+fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
-pub fn load_contributors() -> Result<HashMap<EntityKey, Contributor>> {
-    load_from_dir(CONTRIBUTORS_PATH, "contributor")
+pub fn load_contributors(root: &Path) -> Result<Loaded<Contributor>> {
+    load_from_dir(root, EntityKind::Contributor)
 }
 
-pub fn load_teams() -> Result<HashMap<EntityKey, Team>> {
-    load_from_dir(TEAMS_PATH, "team")
+pub fn load_teams(root: &Path) -> Result<Loaded<Team>> {
+    load_from_dir(root, EntityKind::Team)
 }
 
-pub fn load_repos() -> Result<HashMap<EntityKey, Repo>> {
-    load_from_dir(REPOS_PATH, "repo")
+pub fn load_repos(root: &Path) -> Result<Loaded<Repo>> {
+    load_from_dir(root, EntityKind::Repo)
 }
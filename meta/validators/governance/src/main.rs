@@ -1,34 +1,162 @@
-mod checks;
-
-use anyhow::{Result, anyhow};
-use checks::{
-    validate_cross_references, validate_file_names, validate_github_users, validate_slack_ids,
-};
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, Parser, Subcommand};
 use colored::Colorize;
 use dotenv::dotenv;
-use governance::loader::{load_contributors, load_repos, load_teams};
+use governance::Governance;
+use governance::checks::Registry;
+use governance::config::Config;
+use governance::diff::GovernanceDiff;
+use governance::loader::resolve_root;
 use governance::model::{
-    FileValidationMessages, ValidationError, ValidationReport, ValidationStatistics,
-    ValidationWarning,
+    EntityKind, FileValidationMessages, RetrySummary, ValidationReport, ValidationStatistics,
+};
+use governance::policy;
+use governance::remote::{
+    Cache, Clients, DEFAULT_GITHUB_API_URL, DEFAULT_SLACK_API_URL, HttpOptions,
 };
-use log::error;
-use reqwest::Client;
-use std::{collections::HashMap, path::Path, process};
-
-fn insert_error(files: &mut HashMap<String, FileValidationMessages>, error: ValidationError) {
-    files
-        .entry(error.file.clone())
-        .or_default()
-        .errors
-        .push(error);
+use governance::render::{self, Format};
+use governance::rules::{self, Rule};
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+use std::{collections::BTreeMap, fs::File, io, time::Duration};
+
+#[derive(Parser)]
+#[command(about = "Validates the governance data in this repository")]
+struct Cli {
+    /// Governance root directory (defaults to searching upward from the current directory)
+    #[arg(long, global = true)]
+    root: Option<PathBuf>,
+
+    #[command(flatten)]
+    validate: ValidateArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Args)]
+struct ValidateArgs {
+    /// Output format for the validation report
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Validator config file (defaults to validator.toml in the governance root, if present)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Write the report to this file instead of standard output
+    #[arg(long, global = true)]
+    output: Option<PathBuf>,
+
+    /// Also write the report as SARIF to this file, whatever the format
+    #[arg(long, global = true, value_name = "PATH")]
+    sarif_output: Option<PathBuf>,
+
+    /// Run a check that is off by default (repeatable)
+    #[arg(long, value_name = "CHECK")]
+    enable: Vec<String>,
+
+    /// Skip a check (repeatable)
+    #[arg(long, value_name = "CHECK")]
+    disable: Vec<String>,
+
+    /// Only run checks that don't need GitHub or Slack
+    #[arg(long)]
+    offline: bool,
+
+    /// Base URL of the GitHub REST API
+    #[arg(long, env = "GITHUB_API_URL", default_value = DEFAULT_GITHUB_API_URL)]
+    github_api_url: String,
+
+    /// Base URL of the Slack Web API
+    #[arg(long, env = "SLACK_API_URL", default_value = DEFAULT_SLACK_API_URL)]
+    slack_api_url: String,
+
+    /// Most remote requests in flight at once
+    #[arg(long, default_value_t = HttpOptions::default().max_concurrency)]
+    max_concurrency: usize,
+
+    /// Seconds to wait for each remote request
+    #[arg(long, value_name = "SECONDS", default_value_t = HttpOptions::default().timeout.as_secs())]
+    request_timeout: u64,
+
+    /// Retries for rate-limited or failed remote requests
+    #[arg(long, default_value_t = HttpOptions::default().max_retries)]
+    max_retries: u32,
+
+    /// Cache successful GitHub and Slack lookups on disk between runs
+    #[arg(long, conflicts_with = "no_cache")]
+    cache: bool,
+
+    /// Don't use the lookup cache, even if validator.toml enables it
+    #[arg(long)]
+    no_cache: bool,
 }
 
-fn insert_warning(files: &mut HashMap<String, FileValidationMessages>, warning: ValidationWarning) {
-    files
-        .entry(warning.file.clone())
-        .or_default()
-        .warnings
-        .push(warning);
+#[derive(Subcommand)]
+enum Command {
+    /// Inspect the available checks
+    Checks {
+        /// List every check with its default severity
+        #[arg(long, required = true)]
+        list: bool,
+    },
+    /// Check who may add the given contributor and team files in a pull request
+    Policy {
+        /// GitHub login of the pull request's author, e.g. `octocat` or `dependabot[bot]`
+        #[arg(long)]
+        author: String,
+
+        /// Files the pull request adds, relative to the governance root
+        files: Vec<PathBuf>,
+    },
+    /// Check who may make the changes between two git revisions
+    Diff {
+        /// Revision to compare against, e.g. the pull request's base
+        #[arg(long)]
+        base: String,
+
+        /// Revision with the changes
+        #[arg(long, default_value = "HEAD")]
+        head: String,
+
+        /// GitHub login of whoever made the changes, e.g. `octocat` or `dependabot[bot]`
+        #[arg(long)]
+        author: String,
+    },
+}
+
+/// Lists every check, with the severity and on/off state `selected` runs
+/// them with.
+fn list_checks(all: &Registry, selected: &Registry) {
+    let width = all.infos().map(|check| check.id().len()).max().unwrap_or(0);
+    for check in all.infos() {
+        let state = if selected.infos().any(|c| c.id() == check.id()) {
+            ""
+        } else if check.enabled_by_default() {
+            " (disabled)"
+        } else {
+            " (opt-in)"
+        };
+        println!(
+            "{:<width$} {:<8} {}{}",
+            check.id().bold(),
+            selected.severity(check),
+            check.description(),
+            state.dimmed(),
+            width = width
+        );
+    }
+}
+
+/// The checks to run, as `args` and `config` select and configure them.
+fn select_checks(clients: &Clients, config: &Config, args: &ValidateArgs) -> Result<Registry> {
+    let enable = [args.enable.clone(), config.checks.enable.clone()].concat();
+    let disable = [args.disable.clone(), config.checks.disable.clone()].concat();
+    // Severities first, so that one set for a disabled check is still valid
+    Registry::new(clients, config)
+        .with_severities(&config.checks.severity)?
+        .select(&enable, &disable)
 }
 
 #[tokio::main]
@@ -36,75 +164,130 @@ async fn main() -> Result<()> {
     dotenv().ok();
     env_logger::init();
 
-    // Ensure this is being run from the workspace root
-    if !Path::new("contributors").exists() {
-        error!("Please run this binary from the workspace root.");
-        process::exit(1);
+    let cli = Cli::parse();
+    if let Some(Command::Policy { author, files }) = &cli.command {
+        let root = resolve_root(cli.root.as_deref())?;
+        return check_policy(&root, author, files, &cli.validate);
+    }
+    if let Some(Command::Diff { base, head, author }) = &cli.command {
+        let root = resolve_root(cli.root.as_deref())?;
+        return check_diff(&root, base, head, author, &cli.validate);
     }
 
-    // Load data from files
-    let contributors = load_contributors()?;
-    let teams = load_teams()?;
-    let repos = load_repos()?;
+    let http_options = HttpOptions {
+        max_concurrency: cli.validate.max_concurrency,
+        timeout: Duration::from_secs(cli.validate.request_timeout),
+        max_retries: cli.validate.max_retries,
+        ..HttpOptions::default()
+    };
+    let clients = Clients::from_env(
+        &cli.validate.github_api_url,
+        &cli.validate.slack_api_url,
+        http_options,
+    );
 
-    let mut file_messages = contributors
-        .keys()
-        .map(|k| {
-            (
-                format!("contributors/{}.toml", k),
-                FileValidationMessages::default(),
-            )
-        })
-        .chain(teams.keys().map(|k| {
-            (
-                format!("teams/{}.toml", k),
-                FileValidationMessages::default(),
-            )
-        }))
-        .chain(repos.keys().map(|k| {
-            (
-                format!("repos/{}.toml", k),
-                FileValidationMessages::default(),
-            )
-        }))
-        .collect::<HashMap<_, _>>();
+    let args = cli.validate;
+    let root = resolve_root(cli.root.as_deref())?;
+    let config = Config::load(&root, args.config.as_deref())?;
 
-    // Validate file names
-    for error in validate_file_names(&contributors, &teams, &repos) {
-        insert_error(&mut file_messages, error);
+    if let Some(Command::Checks { .. }) = cli.command {
+        let all = Registry::new(&clients, &config);
+        list_checks(&all, &select_checks(&clients, &config, &args)?);
+        return Ok(());
     }
 
-    // Validate cross-references
-    for error in validate_cross_references(&contributors, &teams, &repos) {
-        insert_error(&mut file_messages, error);
+    let cache = (args.cache || (config.cache.enable && !args.no_cache)).then(|| {
+        Cache::open(
+            &root.join(&config.cache.path),
+            Duration::from_secs(config.cache.ttl_hours * 3600),
+        )
+    });
+    let clients = match &cache {
+        Some(cache) => clients.with_cache(cache.clone()),
+        None => clients,
+    };
+    let registry = select_checks(&clients, &config, &args)?;
+    let (registry, skipped_checks) = registry.without_unavailable(args.offline);
+    for skipped in &skipped_checks {
+        info!("Skipping {}: {}", skipped.check, skipped.reason);
     }
 
-    let client = Client::new();
+    // Load data from files, keeping whatever parses
+    let governance = Governance::load(&root)?;
 
-    // Validate GitHub users
-    let (errors, warnings) = validate_github_users(&contributors, &client).await;
-    for error in errors {
-        insert_error(&mut file_messages, error);
+    let mut file_messages = governance
+        .entities()
+        .map(|(key, _)| (key.file(), FileValidationMessages::default()))
+        .collect::<BTreeMap<_, _>>();
+
+    // Report files that failed to load
+    for error in governance.load_errors() {
+        file_messages
+            .entry(error.file.clone())
+            .or_default()
+            .errors
+            .push(error.clone());
     }
-    for warning in warnings {
-        insert_warning(&mut file_messages, warning);
+
+    // Run every selected check
+    for diagnostic in registry.run(&governance).await {
+        file_messages
+            .entry(diagnostic.file.clone())
+            .or_default()
+            .push(diagnostic);
     }
 
-    // Validate Slack IDs
-    let (errors, warnings) = validate_slack_ids(&contributors, &teams, &client).await;
-    for error in errors {
-        insert_error(&mut file_messages, error);
+    // A cache that can't be written only costs requests next time
+    if let Some(cache) = &cache
+        && let Err(e) = cache.save()
+    {
+        warn!("Failed to save the lookup cache: {:#}", e);
     }
-    for warning in warnings {
-        insert_warning(&mut file_messages, warning);
+
+    let stats = statistics(&mut file_messages, |kind| governance.count(kind));
+    let report = ValidationReport {
+        valid: stats.total_errors == 0,
+        stats,
+        files: file_messages,
+        skipped_checks,
+        network: clients.summary(),
+    };
+
+    let rules = std::iter::once(rules::INVALID_FILE)
+        .chain(registry.infos().map(|check| Rule {
+            id: check.id(),
+            description: check.description(),
+            default_severity: registry.severity(check),
+        }))
+        .collect::<Vec<_>>();
+
+    emit(&report, &rules, &args)
+}
+
+/// Counts up `file_messages`, after ordering each file's messages by
+/// location. `count` gives the number of entities of each kind. Only data
+/// files count as valid or invalid; findings about the data as a whole, e.g.
+/// reported against the config file, only count towards the totals.
+fn statistics(
+    file_messages: &mut BTreeMap<String, FileValidationMessages>,
+    count: impl Fn(EntityKind) -> usize,
+) -> ValidationStatistics {
+    for messages in file_messages.values_mut() {
+        messages.errors.sort_by_key(|e| e.span);
+        messages.warnings.sort_by_key(|w| w.span);
     }
 
-    // Generate validation report
     let total_errors = file_messages.values().map(|f| f.errors.len()).sum();
     let total_warnings = file_messages.values().map(|f| f.warnings.len()).sum();
 
-    let (valid_files_count, invalid_files_count) =
-        file_messages.values().fold((0, 0), |(valid, invalid), m| {
+    let data_dirs = [EntityKind::Contributor, EntityKind::Team, EntityKind::Repo].map(|k| k.dir());
+    let (valid_files_count, invalid_files_count) = file_messages
+        .iter()
+        .filter(|(file, _)| {
+            file.split_once('/')
+                .is_some_and(|(dir, _)| data_dirs.contains(&dir))
+        })
+        .fold((0, 0), |(valid, invalid), (_, m)| {
             if m.errors.is_empty() {
                 (valid + 1, invalid)
             } else {
@@ -112,69 +295,155 @@ async fn main() -> Result<()> {
             }
         });
 
-    let stats = ValidationStatistics {
-        contributors_count: contributors.len(),
-        teams_count: teams.len(),
-        repos_count: repos.len(),
+    ValidationStatistics {
+        contributors_count: count(EntityKind::Contributor),
+        teams_count: count(EntityKind::Team),
+        repos_count: count(EntityKind::Repo),
         valid_files_count,
         invalid_files_count,
         total_errors,
         total_warnings,
-    };
+    }
+}
 
-    let report = ValidationReport {
-        valid: stats.invalid_files_count == 0,
-        stats,
-        files: file_messages,
-    };
+/// Writes `report` in the requested format, and as SARIF if asked to,
+/// failing if it has any errors.
+fn emit(report: &ValidationReport, rules: &[Rule], args: &ValidateArgs) -> Result<()> {
+    match &args.output {
+        Some(path) => render_to(path, args.format, report, rules)?,
+        None => render::render(args.format, report, rules, &mut io::stdout().lock())?,
+    }
+    if let Some(path) = &args.sarif_output {
+        render_to(path, Format::Sarif, report, rules)?;
+    }
 
-    // Display report
-    println!("{}", "===== SUMMARY =====".blue().bold());
-    println!("Contributors: {}", report.stats.contributors_count);
-    println!("Teams: {}", report.stats.teams_count);
-    println!("Repos: {}", report.stats.repos_count);
-    println!("Valid files: {}", report.stats.valid_files_count);
-    println!("Invalid files: {}", report.stats.invalid_files_count);
-    println!("Total errors: {}", report.stats.total_errors);
-    println!("Total warnings: {}", report.stats.total_warnings);
-
-    if report.stats.total_errors > 0 {
-        println!("\n{}", "===== ERRORS =====".red().bold());
-        for (file, messages) in &report.files {
-            if messages.errors.is_empty() {
-                continue;
-            }
+    if !report.valid {
+        // Every file with errors, including any that aren't data files
+        let files = report.files.values().filter(|m| !m.errors.is_empty());
+        let (errors, files) = (
+            report.stats.total_errors.to_string(),
+            files.count().to_string(),
+        );
+        return Err(match args.format {
+            Format::Text => anyhow!(
+                "Validation failed with {} error(s) in {} file(s)",
+                errors.red().bold(),
+                files.red().bold()
+            ),
+            _ => anyhow!(
+                "Validation failed with {} error(s) in {} file(s)",
+                errors,
+                files
+            ),
+        });
+    }
 
-            println!("{}", file);
-            for error in &messages.errors {
-                println!("  - {}", error.message);
-            }
+    Ok(())
+}
+
+fn render_to(path: &Path, format: Format, report: &ValidationReport, rules: &[Rule]) -> Result<()> {
+    let mut file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    render::render(format, report, rules, &mut file)
+}
+
+/// Applies the pull request policy to the `files` added by `author`, and
+/// reports the result like a validation run.
+fn check_policy(root: &Path, author: &str, files: &[PathBuf], args: &ValidateArgs) -> Result<()> {
+    let checked = policy::check(root, author, files);
+
+    let mut file_messages = BTreeMap::<_, FileValidationMessages>::new();
+    for file in &checked {
+        let messages = file_messages.entry(file.file.clone()).or_default();
+        for diagnostic in &file.diagnostics {
+            messages.push(diagnostic.clone());
         }
     }
 
-    if report.stats.total_warnings > 0 {
-        println!("\n{}", "===== WARNINGS =====".yellow().bold());
-        for (file, messages) in &report.files {
-            if messages.warnings.is_empty() {
-                continue;
-            }
+    emit_policy(
+        file_messages,
+        |kind| checked.iter().filter(|file| file.kind == kind).count(),
+        args,
+    )
+}
 
-            println!("{}", file);
-            for warning in &messages.warnings {
-                println!("  - {}", warning.message);
-            }
+/// Applies the pull request policy to what changed from `base` to `head`,
+/// both read from git, and reports the result like a validation run.
+fn check_diff(
+    root: &Path,
+    base: &str,
+    head: &str,
+    author: &str,
+    args: &ValidateArgs,
+) -> Result<()> {
+    let base = Governance::load_revision(root, base)?;
+    let head = Governance::load_revision(root, head)?;
+    let diff = GovernanceDiff::between(&base, &head);
+
+    let mut file_messages = BTreeMap::<_, FileValidationMessages>::new();
+    for entity in &diff.entities {
+        info!("{}: {}", entity.key.file(), entity.change);
+        file_messages.entry(entity.key.file()).or_default();
+    }
+    for (list, change) in [("member", &diff.members), ("repo", &diff.repos)]
+        .into_iter()
+        .flat_map(|(list, changes)| changes.iter().map(move |change| (list, change)))
+    {
+        info!(
+            "{}: {} {} {}",
+            change.team.file(),
+            list,
+            change.name,
+            change.change
+        );
+    }
+
+    // Files the changes broke, rather than ones that were broken already
+    for error in head.load_errors() {
+        let known = base
+            .load_errors()
+            .any(|e| e.file == error.file && e.message == error.message);
+        if !known {
+            file_messages
+                .entry(error.file.clone())
+                .or_default()
+                .errors
+                .push(error.clone());
         }
     }
 
-    if !report.valid {
-        println!();
-        return Err(anyhow!(
-            "Validation failed with {} error(s) in {} file(s)",
-            report.stats.total_errors.to_string().red().bold(),
-            report.stats.invalid_files_count.to_string().red().bold()
-        ));
+    for diagnostic in policy::check_diff(&diff, &base, &head, author) {
+        file_messages
+            .entry(diagnostic.file.clone())
+            .or_default()
+            .push(diagnostic);
     }
 
-    println!("\n{}", "Validation passed!".green().bold());
-    Ok(())
+    emit_policy(
+        file_messages,
+        |kind| diff.entities.iter().filter(|e| e.key.kind == kind).count(),
+        args,
+    )
+}
+
+/// Reports the policy's findings in `file_messages`. `count` gives the
+/// number of files of each kind the policy looked at.
+fn emit_policy(
+    mut file_messages: BTreeMap<String, FileValidationMessages>,
+    count: impl Fn(EntityKind) -> usize,
+    args: &ValidateArgs,
+) -> Result<()> {
+    let stats = statistics(&mut file_messages, count);
+    let report = ValidationReport {
+        valid: stats.total_errors == 0,
+        stats,
+        files: file_messages,
+        skipped_checks: Vec::new(),
+        network: RetrySummary::default(),
+    };
+
+    let rules = std::iter::once(rules::INVALID_FILE)
+        .chain(policy::RULES)
+        .collect::<Vec<_>>();
+    emit(&report, &rules, args)
 }
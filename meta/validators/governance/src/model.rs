@@ -1,26 +1,167 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Display, Formatter, Result};
-use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Implements the conversions shared by the string-backed identifier types.
+/// Each type provides its own `validate`.
+macro_rules! string_id {
+    ($name:ident) => {
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = String;
+
+            fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+                Self::validate(&value)?;
+                Ok(Self(value))
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                Self::try_from(s.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+/// Describes what kind of Slack ID `id` looks like, for error messages.
+fn describe_slack_id(id: &str) -> &'static str {
+    match id.chars().next() {
+        Some('U') | Some('W') => "a member ID",
+        Some('C') | Some('G') => "a channel ID",
+        Some('T') => "a workspace ID",
+        _ => "not a Slack ID",
+    }
+}
+
+fn is_slack_id_body(body: &str) -> bool {
+    !body.is_empty()
+        && body
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// A GitHub login: alphanumerics and single hyphens, not starting or ending
+/// with a hyphen, at most 39 characters.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct GithubLogin(String);
+
+impl GithubLogin {
+    fn validate(login: &str) -> std::result::Result<(), String> {
+        let valid = !login.is_empty()
+            && login.len() <= 39
+            && !login.starts_with('-')
+            && !login.ends_with('-')
+            && !login.contains("--")
+            && login.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("'{}' is not a valid GitHub username", login))
+        }
+    }
+}
+
+string_id!(GithubLogin);
+
+/// A Slack member ID, e.g. `U07FPJKFB5E`, or `W...` for members of an
+/// Enterprise Grid organization.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct SlackUserId(String);
+
+impl SlackUserId {
+    fn validate(id: &str) -> std::result::Result<(), String> {
+        match id.strip_prefix(['U', 'W']) {
+            Some(body) if is_slack_id_body(body) => Ok(()),
+            _ => Err(format!(
+                "'{}' is not a Slack member ID (expected 'U' or 'W' followed by uppercase letters and digits, found {})",
+                id,
+                describe_slack_id(id)
+            )),
+        }
+    }
+}
+
+string_id!(SlackUserId);
+
+/// A Slack channel ID, `C...` for public channels and `G...` for private ones.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct SlackChannelId(String);
+
+impl SlackChannelId {
+    fn validate(id: &str) -> std::result::Result<(), String> {
+        match id.strip_prefix(['C', 'G']) {
+            Some(body) if is_slack_id_body(body) => Ok(()),
+            _ => Err(format!(
+                "'{}' is not a Slack channel ID (expected 'C' or 'G' followed by uppercase letters and digits, found {})",
+                id,
+                describe_slack_id(id)
+            )),
+        }
+    }
+}
+
+string_id!(SlackChannelId);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all(deserialize = "kebab-case", serialize = "camelCase"))]
 pub struct Contributor {
     pub full_name: String,
-    pub github_username: String,
-    pub slack_member_id: String,
+    pub github_username: GithubLogin,
+    pub slack_member_id: SlackUserId,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all(deserialize = "kebab-case", serialize = "camelCase"))]
 pub struct Team {
     pub name: String,
     pub members: Vec<String>,
     pub repos: Vec<String>,
-    pub slack_channel_ids: Vec<String>,
+    pub slack_channel_ids: Vec<SlackChannelId>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Repo {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -31,48 +172,236 @@ pub struct Repo {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+/// The three kinds of governance entity, one per data directory.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    Contributor,
+    Team,
+    Repo,
+}
+
+impl EntityKind {
+    /// The data directory holding this kind's files, relative to the root.
+    pub const fn dir(self) -> &'static str {
+        match self {
+            EntityKind::Contributor => "contributors",
+            EntityKind::Team => "teams",
+            EntityKind::Repo => "repos",
+        }
+    }
+}
+
+impl Display for EntityKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.pad(match self {
+            EntityKind::Contributor => "contributor",
+            EntityKind::Team => "team",
+            EntityKind::Repo => "repo",
+        })
+    }
+}
+
+/// Identifies one entity by its kind and file stem. Entities of different
+/// kinds may share a name, e.g. a team and a repo both called `governance`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct EntityKey {
-    pub kind: String, // "repo", "team", "contributor"
+    pub kind: EntityKind,
     pub name: String, // file_stem
 }
 
 impl EntityKey {
+    pub fn new(kind: EntityKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+        }
+    }
+
+    pub fn contributor(name: impl Into<String>) -> Self {
+        Self::new(EntityKind::Contributor, name)
+    }
+
+    pub fn team(name: impl Into<String>) -> Self {
+        Self::new(EntityKind::Team, name)
+    }
+
+    pub fn repo(name: impl Into<String>) -> Self {
+        Self::new(EntityKind::Repo, name)
+    }
+
     pub fn scoped_id(&self) -> String {
         format!("{}:{}", self.kind, self.name)
     }
+
+    /// The file defining this entity, relative to the governance root.
+    pub fn file(&self) -> String {
+        format!("{}/{}.toml", self.kind.dir(), self.name)
+    }
 }
 
-impl PartialEq for EntityKey {
-    fn eq(&self, other: &Self) -> bool {
-        self.name == other.name
+impl Display for EntityKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.name)
     }
 }
 
-impl Eq for EntityKey {}
+/// A loaded entity of any kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entity {
+    Contributor(Contributor),
+    Team(Team),
+    Repo(Repo),
+}
 
-impl Hash for EntityKey {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.name.hash(state);
+impl Entity {
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            Entity::Contributor(_) => EntityKind::Contributor,
+            Entity::Team(_) => EntityKind::Team,
+            Entity::Repo(_) => EntityKind::Repo,
+        }
     }
 }
 
-impl Display for EntityKey {
+impl From<Contributor> for Entity {
+    fn from(contributor: Contributor) -> Self {
+        Entity::Contributor(contributor)
+    }
+}
+
+impl From<Team> for Entity {
+    fn from(team: Team) -> Self {
+        Entity::Team(team)
+    }
+}
+
+impl From<Repo> for Entity {
+    fn from(repo: Repo) -> Self {
+        Entity::Repo(repo)
+    }
+}
+
+/// A 1-based line and column (in characters) within a source file.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The source range of a value, with an exclusive `end`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Display for Span {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{}", self.name)
+        write!(f, "{}:{}", self.start.line, self.start.column)
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Source locations of the values in one loaded file, keyed by TOML path,
+/// e.g. `members` for the whole array and `members[2]` for one of its items.
+#[derive(Debug, Clone, Default)]
+pub struct SourceSpans {
+    pub(crate) spans: HashMap<String, Span>,
+}
+
+impl SourceSpans {
+    pub fn field(&self, key: &str) -> Option<Span> {
+        self.spans.get(key).copied()
+    }
+
+    pub fn item(&self, key: &str, index: usize) -> Option<Span> {
+        self.spans.get(&format!("{}[{}]", key, index)).copied()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.pad(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// A single finding produced by a check, before it is filed into the report
+/// as an error or a warning.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    pub file: String,
+    pub message: String,
+    pub span: Option<Span>,
+    /// Set when the check couldn't tell either way, e.g. because a remote
+    /// lookup failed. Such findings keep their severity whatever the check's
+    /// findings are configured to be reported at.
+    pub inconclusive: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ValidationError {
+    pub rule: String,
     pub file: String,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ValidationWarning {
+    pub rule: String,
     pub file: String,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+}
+
+impl From<Diagnostic> for ValidationError {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self {
+            rule: diagnostic.rule,
+            file: diagnostic.file,
+            message: diagnostic.message,
+            span: diagnostic.span,
+        }
+    }
+}
+
+/// A file that failed to load, which is always an error.
+impl From<ValidationError> for Diagnostic {
+    fn from(error: ValidationError) -> Self {
+        Self {
+            rule: error.rule,
+            severity: Severity::Error,
+            file: error.file,
+            message: error.message,
+            span: error.span,
+            inconclusive: false,
+        }
+    }
+}
+
+impl From<Diagnostic> for ValidationWarning {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self {
+            rule: diagnostic.rule,
+            file: diagnostic.file,
+            message: diagnostic.message,
+            span: diagnostic.span,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -83,6 +412,15 @@ pub struct FileValidationMessages {
     pub warnings: Vec<ValidationWarning>,
 }
 
+impl FileValidationMessages {
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        match diagnostic.severity {
+            Severity::Error => self.errors.push(diagnostic.into()),
+            Severity::Warning => self.warnings.push(diagnostic.into()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidationStatistics {
     pub contributors_count: usize,
@@ -94,9 +432,40 @@ pub struct ValidationStatistics {
     pub total_warnings: usize,
 }
 
+/// A check that was selected but could not run, e.g. a remote check in
+/// offline mode.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SkippedCheck {
+    pub check: String,
+    pub reason: String,
+}
+
+/// How the remote requests made by the checks went.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetrySummary {
+    pub requests: usize,
+    /// Requests that needed at least one retry.
+    pub retried: usize,
+    /// Requests still failing when their retries ran out.
+    pub gave_up: usize,
+    /// Lookups answered from the on-disk cache without a request.
+    #[serde(default)]
+    pub cached: usize,
+}
+
+impl RetrySummary {
+    pub fn is_empty(&self) -> bool {
+        self.requests == 0 && self.cached == 0
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidationReport {
     pub valid: bool,
     pub stats: ValidationStatistics,
-    pub files: HashMap<String, FileValidationMessages>,
+    pub files: BTreeMap<String, FileValidationMessages>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_checks: Vec<SkippedCheck>,
+    #[serde(default, skip_serializing_if = "RetrySummary::is_empty")]
+    pub network: RetrySummary,
 }
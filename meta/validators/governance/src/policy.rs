@@ -0,0 +1,206 @@
+use crate::Governance;
+use crate::diff::{Change, GovernanceDiff};
+use crate::loader::load_file;
+use crate::model::{Contributor, Diagnostic, Entity, EntityKind, Severity, Span, Team};
+use crate::rules::Rule;
+use std::path::{Component, Path};
+
+pub const SELF_NOMINATION: Rule = Rule {
+    id: "contributor-self-nomination",
+    description: "A new contributor file must be submitted by that contributor",
+    default_severity: Severity::Error,
+};
+
+pub const TEAM_CREATOR_MEMBERSHIP: Rule = Rule {
+    id: "team-creator-membership",
+    description: "Whoever adds a team must be listed among its members",
+    default_severity: Severity::Error,
+};
+
+pub const MEMBER_REMOVAL: Rule = Rule {
+    id: "team-member-removal",
+    description: "Only the member or a fellow team member may remove someone from a team",
+    default_severity: Severity::Error,
+};
+
+pub const RULES: [Rule; 3] = [SELF_NOMINATION, TEAM_CREATOR_MEMBERSHIP, MEMBER_REMOVAL];
+
+/// What the policy found in one file a pull request adds.
+#[derive(Debug)]
+pub struct FilePolicy {
+    pub file: String,
+    pub kind: EntityKind,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Applies the rules about who may add what to the `added` files of a pull
+/// request by `author`. Unlike the checks, these depend on who made the
+/// change rather than only on the data. Paths are relative to `root`;
+/// anything that isn't a contributor or team file is left alone.
+///
+/// `author` is whatever GitHub reports, which for an app is a bot login such
+/// as `dependabot[bot]`. A bot can't be a contributor or team member, so it
+/// is held to the same rules and fails any that need a particular person,
+/// while changes the rules don't cover pass as for anyone else.
+pub fn check(root: &Path, author: &str, added: &[impl AsRef<Path>]) -> Vec<FilePolicy> {
+    added
+        .iter()
+        .filter_map(|path| governed_file(path.as_ref()))
+        .filter_map(|(kind, file)| {
+            let diagnostics = match kind {
+                EntityKind::Contributor => check_contributor(root, author, &file),
+                EntityKind::Team => check_team(root, author, &file),
+                EntityKind::Repo => return None,
+            };
+            Some(FilePolicy {
+                file,
+                kind,
+                diagnostics,
+            })
+        })
+        .collect()
+}
+
+/// The kind and root-relative name of `path`, if it is a TOML file directly
+/// in one of the data directories.
+fn governed_file(path: &Path) -> Option<(EntityKind, String)> {
+    let parts = path
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .map(|component| match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let [dir, name] = parts[..] else {
+        return None;
+    };
+    if !name.ends_with(".toml") {
+        return None;
+    }
+    let kind = [EntityKind::Contributor, EntityKind::Team, EntityKind::Repo]
+        .into_iter()
+        .find(|kind| kind.dir() == dir)?;
+    Some((kind, format!("{}/{}", dir, name)))
+}
+
+fn check_contributor(root: &Path, author: &str, file: &str) -> Vec<Diagnostic> {
+    match load_file::<Contributor>(root, &root.join(file), EntityKind::Contributor) {
+        Ok((contributor, spans)) => {
+            self_nomination(file, &contributor, spans.field("github-username"), author)
+                .into_iter()
+                .collect()
+        }
+        Err(error) => vec![error.into()],
+    }
+}
+
+fn check_team(root: &Path, author: &str, file: &str) -> Vec<Diagnostic> {
+    match load_file::<Team>(root, &root.join(file), EntityKind::Team) {
+        Ok((team, spans)) => creator_membership(file, &team, spans.field("members"), author)
+            .into_iter()
+            .collect(),
+        Err(error) => vec![error.into()],
+    }
+}
+
+/// Applies the rules about who may make which change to everything `diff`
+/// changes from `base` to `head` in a pull request by `author`. Additions
+/// are held to the same rules as [`check`].
+pub fn check_diff(
+    diff: &GovernanceDiff,
+    base: &Governance,
+    head: &Governance,
+    author: &str,
+) -> Vec<Diagnostic> {
+    let added = diff
+        .with(Change::Added)
+        .filter_map(|key| match head.get(key)? {
+            Entity::Contributor(contributor) => self_nomination(
+                &key.file(),
+                contributor,
+                head.field_span(key, "github-username"),
+                author,
+            ),
+            Entity::Team(team) => {
+                creator_membership(&key.file(), team, head.field_span(key, "members"), author)
+            }
+            Entity::Repo(_) => None,
+        });
+
+    let removed = diff
+        .members
+        .iter()
+        .filter(|member| member.change == Change::Removed)
+        .filter_map(|member| {
+            let team = base.team(&member.team.name)?;
+            if is(author, &member.name) || team.members.iter().any(|m| is(author, m)) {
+                return None;
+            }
+            let file = member.team.file();
+            Some(Diagnostic {
+                rule: MEMBER_REMOVAL.id.to_string(),
+                severity: MEMBER_REMOVAL.default_severity,
+                message: format!(
+                    "Only {} or a member of team '{}' may remove them from {}, not {}",
+                    member.name, member.team, file, author
+                ),
+                span: head.field_span(&member.team, "members"),
+                file,
+                inconclusive: false,
+            })
+        });
+
+    added.chain(removed).collect()
+}
+
+fn self_nomination(
+    file: &str,
+    contributor: &Contributor,
+    span: Option<Span>,
+    author: &str,
+) -> Option<Diagnostic> {
+    let github = &contributor.github_username;
+    if is(author, github.as_str()) {
+        return None;
+    }
+    Some(Diagnostic {
+        rule: SELF_NOMINATION.id.to_string(),
+        severity: SELF_NOMINATION.default_severity,
+        file: file.to_string(),
+        message: format!(
+            "Contributor file {} must be submitted by {} themselves, not by {}",
+            file, github, author
+        ),
+        span,
+        inconclusive: false,
+    })
+}
+
+fn creator_membership(
+    file: &str,
+    team: &Team,
+    span: Option<Span>,
+    author: &str,
+) -> Option<Diagnostic> {
+    if team.members.iter().any(|member| is(author, member)) {
+        return None;
+    }
+    Some(Diagnostic {
+        rule: TEAM_CREATOR_MEMBERSHIP.id.to_string(),
+        severity: TEAM_CREATOR_MEMBERSHIP.default_severity,
+        file: file.to_string(),
+        message: format!(
+            "Team creator {} must be listed as a member in {}",
+            author, file
+        ),
+        span,
+        inconclusive: false,
+    })
+}
+
+/// Whether `author` is `login`. GitHub logins are case-insensitive.
+fn is(author: &str, login: &str) -> bool {
+    author.eq_ignore_ascii_case(login)
+}
@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Where the cache is kept by default, relative to the governance root.
+pub const DEFAULT_CACHE_FILE: &str = "target/governance-cache.json";
+
+/// How long a confirmed lookup is trusted by default.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A remote identity that was confirmed to exist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// Unix timestamp of the last confirmation.
+    confirmed_at: u64,
+    /// GitHub's `ETag` for the REST resource, to revalidate it cheaply
+    /// once the entry expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+}
+
+/// An on-disk record of remote lookups that succeeded, so unchanged
+/// identities aren't looked up again on every run. Only existence is cached:
+/// anything missing is always looked up again, so a fix shows up on the next
+/// run. Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    path: PathBuf,
+    ttl: Duration,
+    entries: Arc<Mutex<BTreeMap<String, Entry>>>,
+    hits: Arc<AtomicUsize>,
+}
+
+impl Cache {
+    /// Opens the cache at `path`. A missing or unreadable file starts an
+    /// empty cache, since losing it only costs extra requests.
+    pub fn open(path: &Path, ttl: Duration) -> Self {
+        let entries = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable cache {}: {}", path.display(), e);
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+
+        Self {
+            path: path.to_path_buf(),
+            ttl,
+            entries: Arc::new(Mutex::new(entries)),
+            hits: Arc::default(),
+        }
+    }
+
+    /// Whether `key` was confirmed within the TTL. Counts as a hit if so.
+    pub fn hit(&self, key: &str) -> bool {
+        let fresh = self
+            .entries()
+            .get(key)
+            .is_some_and(|entry| now().saturating_sub(entry.confirmed_at) < self.ttl.as_secs());
+        if fresh {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        fresh
+    }
+
+    /// The `ETag` stored with `key`, fresh or not.
+    pub fn etag(&self, key: &str) -> Option<String> {
+        self.entries().get(key)?.etag.clone()
+    }
+
+    /// Records that `key` exists as of now, keeping its previous `ETag` if
+    /// no new one is given.
+    pub fn confirm(&self, key: &str, etag: Option<String>) {
+        let mut entries = self.entries();
+        let etag = etag.or_else(|| entries.get(key).and_then(|entry| entry.etag.clone()));
+        entries.insert(
+            key.to_string(),
+            Entry {
+                confirmed_at: now(),
+                etag,
+            },
+        );
+    }
+
+    /// Drops `key`, once a lookup found it no longer exists.
+    pub fn forget(&self, key: &str) {
+        self.entries().remove(key);
+    }
+
+    /// Lookups answered without a request so far.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Writes the cache back to its file, creating parent directories.
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(&*self.entries())?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    fn entries(&self) -> MutexGuard<'_, BTreeMap<String, Entry>> {
+        // An entry is always written whole, so a poisoned map is still usable
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
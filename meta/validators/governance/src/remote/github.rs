@@ -0,0 +1,499 @@
+use super::{Cache, Http};
+use crate::model::GithubLogin;
+use anyhow::{Result, anyhow};
+use futures::future::join_all;
+use reqwest::header::{ETAG, IF_NONE_MATCH, LINK};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+
+/// The most lookups sent in one GraphQL query.
+pub const GRAPHQL_BATCH_SIZE: usize = 100;
+
+/// Minimal GitHub client for the lookups the checks need. Lookups of many
+/// users or repos are batched through GraphQL when there is a token, and
+/// fall back to one REST call each when there isn't.
+#[derive(Debug, Clone)]
+pub struct GithubClient {
+    http: Http,
+    base_url: String,
+    token: Option<String>,
+    cache: Option<Cache>,
+}
+
+impl GithubClient {
+    pub fn new(http: Http, base_url: &str, token: Option<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            cache: None,
+        }
+    }
+
+    /// Answers user lookups from `cache` where it can, and records what
+    /// they find in it.
+    pub fn with_cache(self, cache: Cache) -> Self {
+        Self {
+            cache: Some(cache),
+            ..self
+        }
+    }
+
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        let request = self.http.get(url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Whether `github_username` exists. A cached `ETag` makes this a
+    /// conditional request, which GitHub doesn't count against the rate
+    /// limit when nothing changed.
+    pub async fn user_exists(&self, github_username: &GithubLogin) -> Result<bool> {
+        let key = user_key(github_username);
+        let url = format!("{}/users/{}", self.base_url, github_username);
+        let mut request = self.get(&url);
+        if let Some(etag) = self.cache.as_ref().and_then(|cache| cache.etag(&key)) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = self.http.send(request).await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => {
+                let etag = response
+                    .headers()
+                    .get(ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(str::to_string);
+                self.record(&key, true, etag);
+                Ok(true)
+            }
+            StatusCode::NOT_MODIFIED => {
+                self.record(&key, true, None);
+                Ok(true)
+            }
+            StatusCode::NOT_FOUND => {
+                self.record(&key, false, None);
+                Ok(false)
+            }
+            StatusCode::FORBIDDEN => Err(anyhow!("Rate limit exceeded or access forbidden",)),
+            _ => Err(anyhow!("Unexpected status {}", status,)),
+        }
+    }
+
+    /// Whether each of `logins` exists, in order. Logins confirmed within
+    /// the cache's TTL aren't looked up at all, expired ones with an `ETag`
+    /// are revalidated one by one, and the rest are looked up together.
+    pub async fn users_exist(&self, logins: &[&GithubLogin]) -> Vec<Result<bool>> {
+        let mut revalidate = Vec::new();
+        let mut look_up = Vec::new();
+        for (index, login) in logins.iter().enumerate() {
+            let key = user_key(login);
+            match &self.cache {
+                Some(cache) if cache.hit(&key) => {}
+                Some(cache) if cache.etag(&key).is_some() => revalidate.push(index),
+                _ => look_up.push(index),
+            }
+        }
+
+        let unknown = look_up
+            .iter()
+            .map(|&index| logins[index])
+            .collect::<Vec<_>>();
+        let (revalidated, looked_up) = futures::join!(
+            join_all(
+                revalidate
+                    .iter()
+                    .map(|&index| self.user_exists(logins[index]))
+            ),
+            self.look_up_users(&unknown),
+        );
+
+        let mut results = logins.iter().map(|_| Ok(true)).collect::<Vec<_>>();
+        for (index, result) in revalidate
+            .into_iter()
+            .chain(look_up)
+            .zip(revalidated.into_iter().chain(looked_up))
+        {
+            results[index] = result;
+        }
+        results
+    }
+
+    async fn look_up_users(&self, logins: &[&GithubLogin]) -> Vec<Result<bool>> {
+        let Some(token) = &self.token else {
+            return join_all(logins.iter().map(|login| self.user_exists(login))).await;
+        };
+
+        let fields = logins
+            .iter()
+            .map(|login| {
+                format!(
+                    "user(login: {}) {{ login }}",
+                    graphql_string(login.as_str())
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let results = self
+            .graphql_batch(token, &fields)
+            .await
+            .into_iter()
+            .map(|node| node.map(|node| node.is_some()))
+            .collect::<Vec<_>>();
+        for (login, result) in logins.iter().zip(&results) {
+            if let Ok(exists) = result {
+                self.record(&user_key(login), *exists, None);
+            }
+        }
+        results
+    }
+
+    /// Notes in the cache, if any, whether `key` was found to exist.
+    fn record(&self, key: &str, exists: bool, etag: Option<String>) {
+        match &self.cache {
+            Some(cache) if exists => cache.confirm(key, etag),
+            Some(cache) => cache.forget(key),
+            None => {}
+        }
+    }
+
+    /// Looks up each of `names` in `org`, in order, like [`Self::repo`].
+    pub async fn repos(&self, org: &str, names: &[&str]) -> Vec<Result<Option<GithubRepo>>> {
+        let Some(token) = &self.token else {
+            return join_all(names.iter().map(|name| self.repo(org, name))).await;
+        };
+
+        let fields = names
+            .iter()
+            .map(|name| {
+                format!(
+                    "repository(owner: {}, name: {}) {{ name isArchived }}",
+                    graphql_string(org),
+                    graphql_string(name)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        self.graphql_batch(token, &fields)
+            .await
+            .into_iter()
+            .map(|node| {
+                node?
+                    .map(|node| serde_json::from_value(node).map_err(Into::into))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Runs each of `fields` as an aliased lookup, [`GRAPHQL_BATCH_SIZE`] per
+    /// query, and returns their results in order: `None` for anything GitHub
+    /// reports as not found. A failed query fails every lookup in it.
+    async fn graphql_batch(&self, token: &str, fields: &[String]) -> Vec<Result<Option<Value>>> {
+        let mut results = Vec::with_capacity(fields.len());
+
+        for batch in fields.chunks(GRAPHQL_BATCH_SIZE) {
+            match self.graphql_query(token, batch).await {
+                Ok(nodes) => results.extend(nodes),
+                Err(e) => {
+                    let message = e.to_string();
+                    results.extend(batch.iter().map(|_| Err(anyhow!("{}", message))));
+                }
+            }
+        }
+
+        results
+    }
+
+    async fn graphql_query(
+        &self,
+        token: &str,
+        fields: &[String],
+    ) -> Result<Vec<Result<Option<Value>>>> {
+        let selections = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| format!("n{}: {}", index, field))
+            .collect::<Vec<_>>();
+        let query = format!("query {{ {} }}", selections.join(" "));
+        let body = self.graphql(token, json!({ "query": query })).await?;
+
+        // Errors for individual lookups carry the alias as their path
+        let mut errors = HashMap::new();
+        for error in body["errors"].as_array().into_iter().flatten() {
+            let alias = error["path"][0].as_str().unwrap_or_default();
+            errors.insert(alias.to_string(), error);
+        }
+
+        let Some(data) = body.get("data").filter(|data| data.is_object()) else {
+            let message = body["errors"][0]["message"]
+                .as_str()
+                .unwrap_or("Unexpected response from GitHub GraphQL API");
+            return Err(anyhow!("{}", message));
+        };
+
+        Ok((0..fields.len())
+            .map(|index| {
+                let alias = format!("n{}", index);
+                match (&data[&alias], errors.get(&alias)) {
+                    (Value::Null, Some(error)) if error["type"] != "NOT_FOUND" => Err(anyhow!(
+                        "{}",
+                        error["message"].as_str().unwrap_or("GraphQL error")
+                    )),
+                    (Value::Null, _) => Ok(None),
+                    (node, _) => Ok(Some(node.clone())),
+                }
+            })
+            .collect())
+    }
+
+    /// Sends one GraphQL request and returns the response body, which may
+    /// hold `errors` alongside `data`.
+    async fn graphql(&self, token: &str, body: Value) -> Result<Value> {
+        let request = self
+            .http
+            .post(&format!("{}/graphql", self.base_url))
+            .bearer_auth(token)
+            .json(&body);
+        // Rate limits come back as a 200 with an error in the body
+        let (status, body) = self
+            .http
+            .send_reading(request, async |response| {
+                let status = response.status();
+                if status != StatusCode::OK {
+                    return Ok(((status, Value::Null), false));
+                }
+                let body = response.json::<Value>().await?;
+                let rate_limited = body["errors"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|error| error["type"] == "RATE_LIMITED");
+                Ok(((status, body), rate_limited))
+            })
+            .await?;
+
+        match status {
+            StatusCode::OK => Ok(body),
+            StatusCode::UNAUTHORIZED => Err(anyhow!("Invalid authentication")),
+            StatusCode::FORBIDDEN => Err(anyhow!("Rate limit exceeded or access forbidden")),
+            _ => Err(anyhow!("Unexpected status {}", status)),
+        }
+    }
+
+    /// Looks up `org/name`, following renames. `None` if there is no such
+    /// repo, or it is private and the token can't see it.
+    pub async fn repo(&self, org: &str, name: &str) -> Result<Option<GithubRepo>> {
+        let url = format!("{}/repos/{}/{}", self.base_url, org, name);
+        let response = self.http.send(self.get(&url)).await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => Ok(Some(response.json().await?)),
+            StatusCode::NOT_FOUND => Ok(None),
+            StatusCode::FORBIDDEN => Err(anyhow!("Rate limit exceeded or access forbidden",)),
+            _ => Err(anyhow!("Unexpected status {}", status,)),
+        }
+    }
+
+    /// Everyone in `org` the token can see, and everyone invited to it,
+    /// paging through both lists once rather than asking about each user.
+    /// Listing invitations needs org admin rights, so without them only the
+    /// members are known.
+    pub async fn org_roster(&self, org: &str) -> Result<OrgRoster> {
+        let Some(token) = &self.token else {
+            return Err(anyhow!("GITHUB_TOKEN environment variable not set"));
+        };
+        let (members, pending) = futures::join!(
+            self.org_logins(token, org, "membersWithRole"),
+            self.org_logins(token, org, "pendingMembers"),
+        );
+        Ok(OrgRoster {
+            members: members?,
+            pending: pending.ok(),
+        })
+    }
+
+    /// Whether `github_username` belongs to `org`, asked about on its own.
+    /// Needs a token that can read the org's members.
+    pub async fn org_membership(
+        &self,
+        org: &str,
+        github_username: &GithubLogin,
+    ) -> Result<OrgMembership> {
+        let url = format!(
+            "{}/orgs/{}/memberships/{}",
+            self.base_url, org, github_username
+        );
+        let response = self.http.send(self.get(&url)).await?;
+        let status = response.status();
+
+        match status {
+            StatusCode::OK => {
+                let membership: Value = response.json().await?;
+                match membership.get("state").and_then(|v| v.as_str()) {
+                    Some("active") => Ok(OrgMembership::Active),
+                    Some("pending") => Ok(OrgMembership::Pending),
+                    state => Err(anyhow!("Unexpected membership state {:?}", state)),
+                }
+            }
+            StatusCode::NOT_FOUND => Ok(OrgMembership::None),
+            StatusCode::FORBIDDEN => Err(anyhow!("Rate limit exceeded or access forbidden")),
+            _ => Err(anyhow!("Unexpected status {}", status)),
+        }
+    }
+
+    /// The lowercased logins in the `connection` of users of `org`,
+    /// following its cursors.
+    async fn org_logins(
+        &self,
+        token: &str,
+        org: &str,
+        connection: &str,
+    ) -> Result<HashSet<String>> {
+        let query = format!(
+            "query($org: String!, $after: String) {{ organization(login: $org) {{ \
+             {}(first: 100, after: $after) {{ nodes {{ login }} pageInfo {{ hasNextPage endCursor }} }} \
+             }} }}",
+            connection
+        );
+
+        let mut logins = HashSet::new();
+        let mut after = Value::Null;
+        loop {
+            let variables = json!({ "org": org, "after": after });
+            let mut body = self
+                .graphql(token, json!({ "query": query, "variables": variables }))
+                .await?;
+            if let Some(error) = body["errors"][0]["message"].as_str() {
+                return Err(anyhow!("{}", error));
+            }
+
+            let page = body["data"]["organization"][connection].take();
+            if page.is_null() {
+                return Err(anyhow!("Organization {} not found", org));
+            }
+            logins.extend(
+                page["nodes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|node| node["login"].as_str())
+                    .map(str::to_lowercase),
+            );
+
+            match &page["pageInfo"] {
+                info if info["hasNextPage"] == true => after = info["endCursor"].clone(),
+                _ => return Ok(logins),
+            }
+        }
+    }
+
+    /// Every repo in `org` visible to the token, following pagination.
+    pub async fn org_repos(&self, org: &str) -> Result<Vec<GithubRepo>> {
+        let mut repos = Vec::new();
+        let mut next = Some(format!("{}/orgs/{}/repos?per_page=100", self.base_url, org));
+
+        while let Some(url) = next {
+            let response = self.http.send(self.get(&url)).await?;
+            let status = response.status();
+
+            match status {
+                StatusCode::OK => {}
+                StatusCode::NOT_FOUND => return Err(anyhow!("Organization {} not found", org)),
+                StatusCode::FORBIDDEN => {
+                    return Err(anyhow!("Rate limit exceeded or access forbidden"));
+                }
+                _ => return Err(anyhow!("Unexpected status {}", status)),
+            }
+
+            next = response
+                .headers()
+                .get(LINK)
+                .and_then(|link| link.to_str().ok())
+                .and_then(next_link);
+            repos.extend(response.json::<Vec<GithubRepo>>().await?);
+        }
+
+        Ok(repos)
+    }
+}
+
+/// The `rel="next"` URL from a GitHub `Link` header, if any.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let (url, params) = link.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#)
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// Cache key for a user; logins are case-insensitive.
+fn user_key(login: &GithubLogin) -> String {
+    format!("github-user:{}", login.as_str().to_lowercase())
+}
+
+/// Who is in a GitHub organization, by lowercased login.
+#[derive(Debug, Clone, Default)]
+pub struct OrgRoster {
+    /// The members the token can see, which may not be all of them.
+    pub members: HashSet<String>,
+    /// Invited, but haven't accepted yet. `None` if the token can't list
+    /// invitations.
+    pub pending: Option<HashSet<String>>,
+}
+
+impl OrgRoster {
+    /// The standing of `login`, if the roster shows it; logins are
+    /// case-insensitive. Anyone else may be a member hidden from the token,
+    /// so `None` means unknown rather than not a member.
+    pub fn membership(&self, login: &GithubLogin) -> Option<OrgMembership> {
+        let login = login.as_str().to_lowercase();
+        if self.members.contains(&login) {
+            Some(OrgMembership::Active)
+        } else if self.pending.iter().any(|pending| pending.contains(&login)) {
+            Some(OrgMembership::Pending)
+        } else {
+            None
+        }
+    }
+}
+
+/// A user's standing in a GitHub organization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrgMembership {
+    Active,
+    /// Invited, but hasn't accepted yet.
+    Pending,
+    None,
+}
+
+/// `value` as a GraphQL string literal. JSON string escaping is valid
+/// GraphQL, so no argument can break out of its quotes.
+fn graphql_string(value: &str) -> String {
+    Value::from(value).to_string()
+}
+
+/// The parts of a GitHub repository the checks look at, from either the REST
+/// or the GraphQL API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubRepo {
+    pub name: String,
+    #[serde(default, alias = "isArchived")]
+    pub archived: bool,
+}
@@ -0,0 +1,218 @@
+use super::USER_AGENT;
+use crate::model::RetrySummary;
+use anyhow::{Context, Result};
+use log::debug;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+
+/// Limits on how remote requests are made.
+#[derive(Debug, Clone, Copy)]
+pub struct HttpOptions {
+    /// Most requests in flight at once, across every check.
+    pub max_concurrency: usize,
+    /// Time allowed for each attempt at a request.
+    pub timeout: Duration,
+    /// Retries after the first attempt before giving up.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each one after it.
+    pub base_delay: Duration,
+    /// Longest the client will wait before a retry; a rate limit that resets
+    /// later than this is given up on rather than waited out.
+    pub max_delay: Duration,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 8,
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicUsize,
+    retried: AtomicUsize,
+    gave_up: AtomicUsize,
+}
+
+/// The HTTP layer shared by the remote clients: caps concurrency, times out
+/// each attempt, and retries rate-limited or failed requests with backoff.
+/// Clones share the same limits and counters.
+#[derive(Debug, Clone)]
+pub struct Http {
+    client: Client,
+    options: HttpOptions,
+    permits: Arc<Semaphore>,
+    counters: Arc<Counters>,
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Self::new(HttpOptions::default())
+    }
+}
+
+impl Http {
+    pub fn new(options: HttpOptions) -> Self {
+        Self {
+            client: Client::new(),
+            options,
+            permits: Arc::new(Semaphore::new(options.max_concurrency.max(1))),
+            counters: Arc::default(),
+        }
+    }
+
+    pub fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header("User-Agent", USER_AGENT)
+            .timeout(self.options.timeout)
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    /// Sends `request`, retrying while it is rate limited or fails
+    /// transiently. Once retries run out, the last response is returned as
+    /// is, for the caller to report.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        self.send_reading(request, async |response| Ok((response, false)))
+            .await
+    }
+
+    /// Like [`Self::send`], but hands each response to `read`, which also
+    /// says whether its body reports a rate limit that the status doesn't,
+    /// as GitHub's GraphQL API does. Such responses are retried the same way.
+    pub async fn send_reading<T>(
+        &self,
+        request: RequestBuilder,
+        read: impl AsyncFn(Response) -> Result<(T, bool)>,
+    ) -> Result<T> {
+        let request = request.build()?;
+        self.counters.requests.fetch_add(1, Ordering::Relaxed);
+
+        let mut attempt = 0;
+        loop {
+            let this_attempt = request.try_clone().context("Request can't be retried")?;
+            let result = {
+                let _permit = self.permits.acquire().await?;
+                self.client.execute(this_attempt).await
+            };
+
+            let (result, delay) = match result {
+                Ok(response) => {
+                    let delay = self.retry_delay(&response, attempt);
+                    let headers = response.headers().clone();
+                    match read(response).await {
+                        Ok((value, rate_limited)) => {
+                            let delay = delay.or_else(|| {
+                                rate_limited.then(|| {
+                                    requested_delay(&headers)
+                                        .unwrap_or_else(|| self.backoff(attempt))
+                                })
+                            });
+                            (Ok(value), delay)
+                        }
+                        Err(e) => (Err(e), delay),
+                    }
+                }
+                Err(e) if e.is_timeout() || e.is_connect() => {
+                    (Err(e.into()), Some(self.backoff(attempt)))
+                }
+                Err(e) => (Err(e.into()), None),
+            };
+
+            let Some(delay) = delay else {
+                return result;
+            };
+
+            if attempt >= self.options.max_retries || delay > self.options.max_delay {
+                self.counters.gave_up.fetch_add(1, Ordering::Relaxed);
+                return result.with_context(|| format!("Gave up after {} attempt(s)", attempt + 1));
+            }
+
+            if attempt == 0 {
+                self.counters.retried.fetch_add(1, Ordering::Relaxed);
+            }
+            attempt += 1;
+            debug!(
+                "Retrying {} in {:?} (attempt {})",
+                request.url(),
+                delay,
+                attempt + 1
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// How long to wait before retrying after `response`, if it should be
+    /// retried at all.
+    fn retry_delay(&self, response: &Response, attempt: u32) -> Option<Duration> {
+        let headers = response.headers();
+        let rate_limited = match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => true,
+            // GitHub signals rate limits with a 403 as well
+            StatusCode::FORBIDDEN => {
+                headers.contains_key(RETRY_AFTER)
+                    || header_str(headers, "x-ratelimit-remaining") == Some("0")
+            }
+            status => status.is_server_error(),
+        };
+
+        rate_limited.then(|| requested_delay(headers).unwrap_or_else(|| self.backoff(attempt)))
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.options
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.options.max_delay)
+    }
+
+    /// How many requests were made so far, and how many of them needed
+    /// retrying or were given up on.
+    pub fn summary(&self) -> RetrySummary {
+        RetrySummary {
+            requests: self.counters.requests.load(Ordering::Relaxed),
+            retried: self.counters.retried.load(Ordering::Relaxed),
+            gave_up: self.counters.gave_up.load(Ordering::Relaxed),
+            cached: 0,
+        }
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// The wait the server asked for, from `Retry-After` (in seconds) or, once
+/// the quota is used up, GitHub's `X-RateLimit-Reset` (a Unix timestamp).
+fn requested_delay(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(seconds) = header_str(headers, RETRY_AFTER.as_str()) {
+        return seconds.trim().parse().ok().map(Duration::from_secs);
+    }
+
+    if header_str(headers, "x-ratelimit-remaining") != Some("0") {
+        return None;
+    }
+    let reset = header_str(headers, "x-ratelimit-reset")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(reset.saturating_sub(now) + 1))
+}
@@ -0,0 +1,63 @@
+mod cache;
+mod github;
+mod http;
+mod slack;
+
+pub use cache::{Cache, DEFAULT_CACHE_FILE, DEFAULT_CACHE_TTL};
+pub use github::{GRAPHQL_BATCH_SIZE, GithubClient, GithubRepo, OrgMembership, OrgRoster};
+pub use http::{Http, HttpOptions};
+pub use slack::{SlackClient, SlackProfile, SlackStatus, SlackStrategy};
+
+use crate::model::RetrySummary;
+
+pub const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+pub const DEFAULT_SLACK_API_URL: &str = "https://slack.com/api";
+
+const USER_AGENT: &str = "ScottyLabs-Governance-Validator";
+
+/// Reads a token from the environment, treating an empty value as unset.
+pub fn token_from_env(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|token| !token.is_empty())
+}
+
+/// The remote clients shared by all network checks.
+#[derive(Debug, Clone)]
+pub struct Clients {
+    pub github: GithubClient,
+    pub slack: SlackClient,
+    http: Http,
+    cache: Option<Cache>,
+}
+
+impl Clients {
+    /// Clients for the given API base URLs, with tokens taken from
+    /// `GITHUB_TOKEN` and `SLACK_TOKEN`, sharing one HTTP layer.
+    pub fn from_env(github_api_url: &str, slack_api_url: &str, options: HttpOptions) -> Self {
+        let http = Http::new(options);
+        Self {
+            github: GithubClient::new(http.clone(), github_api_url, token_from_env("GITHUB_TOKEN")),
+            slack: SlackClient::new(http.clone(), slack_api_url, token_from_env("SLACK_TOKEN")),
+            http,
+            cache: None,
+        }
+    }
+
+    /// Has both clients share `cache` for their identity lookups.
+    pub fn with_cache(self, cache: Cache) -> Self {
+        Self {
+            github: self.github.with_cache(cache.clone()),
+            slack: self.slack.with_cache(cache.clone()),
+            cache: Some(cache),
+            ..self
+        }
+    }
+
+    /// Retry statistics across every request made by either client, and how
+    /// many lookups the cache answered instead.
+    pub fn summary(&self) -> RetrySummary {
+        RetrySummary {
+            cached: self.cache.as_ref().map_or(0, Cache::hits),
+            ..self.http.summary()
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use super::{Cache, Http};
+use crate::model::{SlackChannelId, SlackUserId};
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// How the client finds out about Slack IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlackStrategy {
+    /// One `users.info` or `conversations.info` call per ID.
+    #[default]
+    Info,
+    /// Page through `users.list` and `conversations.list` once, and answer
+    /// every lookup from that.
+    List,
+}
+
+/// What Slack knows about a member or channel ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlackStatus {
+    Active,
+    /// A deactivated member or an archived channel.
+    Inactive,
+    Missing,
+}
+
+/// The Web API methods and fields for one kind of Slack object.
+struct Kind {
+    info: &'static str,
+    list: &'static str,
+    /// The `info` query parameter and response field holding the object.
+    param: &'static str,
+    /// The `list` response field holding the page of objects.
+    items: &'static str,
+    /// The object field that is true once it is no longer active.
+    inactive: &'static str,
+    list_query: &'static [(&'static str, &'static str)],
+}
+
+const USERS: Kind = Kind {
+    info: "users.info",
+    list: "users.list",
+    param: "user",
+    items: "members",
+    inactive: "deleted",
+    list_query: &[("limit", "200")],
+};
+
+const CHANNELS: Kind = Kind {
+    info: "conversations.info",
+    list: "conversations.list",
+    param: "channel",
+    items: "channels",
+    inactive: "is_archived",
+    list_query: &[
+        ("limit", "1000"),
+        ("types", "public_channel,private_channel"),
+        ("exclude_archived", "false"),
+    ],
+};
+
+/// The parts of a member's Slack account the identity check looks at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlackProfile {
+    pub real_name: String,
+    pub display_name: String,
+    pub deleted: bool,
+    pub bot: bool,
+}
+
+impl SlackProfile {
+    fn from_user(user: &Value) -> Self {
+        let profile = &user["profile"];
+        let text = |field: &str| {
+            profile[field]
+                .as_str()
+                .or_else(|| user[field].as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        Self {
+            real_name: text("real_name"),
+            display_name: text("display_name"),
+            deleted: user["deleted"].as_bool() == Some(true),
+            // Slackbot is a bot, but isn't flagged as one
+            bot: user["is_bot"].as_bool() == Some(true) || user["id"] == "USLACKBOT",
+        }
+    }
+}
+
+/// Every object of one kind, by ID, or why they couldn't be listed.
+type Listing = Result<HashMap<String, Value>, String>;
+
+/// Minimal Slack Web API client for the lookups the checks need.
+#[derive(Debug, Clone)]
+pub struct SlackClient {
+    http: Http,
+    base_url: String,
+    token: Option<String>,
+    cache: Option<Cache>,
+    strategy: SlackStrategy,
+    users: Arc<OnceCell<Listing>>,
+    channels: Arc<OnceCell<Listing>>,
+}
+
+impl SlackClient {
+    pub fn new(http: Http, base_url: &str, token: Option<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            cache: None,
+            strategy: SlackStrategy::default(),
+            users: Arc::default(),
+            channels: Arc::default(),
+        }
+    }
+
+    /// Answers lookups of IDs confirmed within the TTL from `cache`, and
+    /// records what other lookups find in it.
+    pub fn with_cache(self, cache: Cache) -> Self {
+        Self {
+            cache: Some(cache),
+            ..self
+        }
+    }
+
+    pub fn with_strategy(self, strategy: SlackStrategy) -> Self {
+        Self { strategy, ..self }
+    }
+
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    pub async fn user_status(&self, user: &SlackUserId) -> Result<SlackStatus> {
+        self.status(&USERS, &self.users, user.as_str()).await
+    }
+
+    /// The profile of `user`, or `None` if there is no such member.
+    pub async fn user_profile(&self, user: &SlackUserId) -> Result<Option<SlackProfile>> {
+        let user = self.object(&USERS, &self.users, user.as_str()).await?;
+        Ok(user.as_ref().map(SlackProfile::from_user))
+    }
+
+    pub async fn channel_status(&self, channel: &SlackChannelId) -> Result<SlackStatus> {
+        self.status(&CHANNELS, &self.channels, channel.as_str())
+            .await
+    }
+
+    async fn status(
+        &self,
+        kind: &Kind,
+        listing: &OnceCell<Listing>,
+        slack_id: &str,
+    ) -> Result<SlackStatus> {
+        let key = format!("slack-{}:{}", kind.param, slack_id);
+        if self.cache.as_ref().is_some_and(|cache| cache.hit(&key)) {
+            return Ok(SlackStatus::Active);
+        }
+
+        let status = match self.object(kind, listing, slack_id).await? {
+            Some(object) => status_of(&object, kind),
+            None => SlackStatus::Missing,
+        };
+
+        if let Some(cache) = &self.cache {
+            match status {
+                SlackStatus::Active => cache.confirm(&key, None),
+                _ => cache.forget(&key),
+            }
+        }
+        Ok(status)
+    }
+
+    /// The object with `slack_id`, or `None` if Slack has no such object.
+    async fn object(
+        &self,
+        kind: &Kind,
+        listing: &OnceCell<Listing>,
+        slack_id: &str,
+    ) -> Result<Option<Value>> {
+        // Slack API always requires authentication
+        let Some(token) = &self.token else {
+            return Err(anyhow!("SLACK_TOKEN environment variable not set"));
+        };
+
+        match self.strategy {
+            SlackStrategy::Info => self.info(kind, token, slack_id).await,
+            SlackStrategy::List => {
+                // Every lookup of this kind shares one listing
+                let listing = listing
+                    .get_or_init(|| async {
+                        self.list(kind, token).await.map_err(|e| e.to_string())
+                    })
+                    .await;
+                match listing {
+                    Ok(objects) => Ok(objects.get(slack_id).cloned()),
+                    Err(message) => Err(anyhow!("{}", message)),
+                }
+            }
+        }
+    }
+
+    async fn info(&self, kind: &Kind, token: &str, slack_id: &str) -> Result<Option<Value>> {
+        let mut json = self
+            .call(kind.info, token, &[(kind.param, slack_id)])
+            .await?;
+
+        match json.get("error").and_then(|v| v.as_str()) {
+            None => Ok(Some(json[kind.param].take())),
+            Some(error) => match api_error(error) {
+                e if e.is::<NotFound>() => Ok(None),
+                e => Err(e),
+            },
+        }
+    }
+
+    /// Every object `kind`'s list method returns, by ID.
+    async fn list(&self, kind: &Kind, token: &str) -> Result<HashMap<String, Value>> {
+        let items = self
+            .paginate(kind.list, token, kind.list_query, kind.items)
+            .await?;
+        Ok(items
+            .into_iter()
+            .filter_map(|item| Some((item["id"].as_str()?.to_string(), item)))
+            .collect())
+    }
+
+    /// The member IDs of `channel`, or `None` if there is no such channel.
+    pub async fn channel_members(
+        &self,
+        channel: &SlackChannelId,
+    ) -> Result<Option<HashSet<String>>> {
+        let Some(token) = &self.token else {
+            return Err(anyhow!("SLACK_TOKEN environment variable not set"));
+        };
+
+        let query = [("channel", channel.as_str()), ("limit", "1000")];
+        match self
+            .paginate("conversations.members", token, &query, "members")
+            .await
+        {
+            Ok(members) => Ok(Some(
+                members
+                    .iter()
+                    .filter_map(|member| member.as_str().map(str::to_string))
+                    .collect(),
+            )),
+            Err(e) if e.is::<NotFound>() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Every item under `items` across the pages of `method`, following
+    /// Slack's cursors.
+    async fn paginate(
+        &self,
+        method: &str,
+        token: &str,
+        query: &[(&str, &str)],
+        items: &str,
+    ) -> Result<Vec<Value>> {
+        let mut all = Vec::new();
+        let mut cursor = String::new();
+
+        loop {
+            let mut query = query.to_vec();
+            if !cursor.is_empty() {
+                query.push(("cursor", &cursor));
+            }
+            let mut json = self.call(method, token, &query).await?;
+            if let Some(error) = json.get("error").and_then(|v| v.as_str()) {
+                return Err(api_error(error));
+            }
+
+            match json[items].take() {
+                Value::Array(page) => all.extend(page),
+                _ => return Err(anyhow!("Unexpected response from Slack API")),
+            }
+
+            cursor = json["response_metadata"]["next_cursor"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            if cursor.is_empty() {
+                return Ok(all);
+            }
+        }
+    }
+
+    /// Calls a Web API method. The body is returned whether Slack reports
+    /// success or not; on failure it has an `error` code.
+    async fn call(&self, method: &str, token: &str, query: &[(&str, &str)]) -> Result<Value> {
+        let request = self
+            .http
+            .get(&format!("{}/{}", self.base_url, method))
+            .query(query)
+            .bearer_auth(token);
+
+        let response = self.http.send(request).await?;
+
+        // Unlike GitHub API, Slack API returns HTTP 200 OK for everything but
+        // rate limiting (a 429, retried by the HTTP layer)
+        // The actual success/failure is in the JSON response
+        let json: Value = response.json().await?;
+
+        match json.get("ok").and_then(|v| v.as_bool()) {
+            Some(true) => Ok(json),
+            Some(false) if json.get("error").is_some_and(Value::is_string) => Ok(json),
+            _ => Err(anyhow!("Unexpected response from Slack API")),
+        }
+    }
+}
+
+fn status_of(object: &Value, kind: &Kind) -> SlackStatus {
+    if object[kind.inactive].as_bool() == Some(true) {
+        SlackStatus::Inactive
+    } else {
+        SlackStatus::Active
+    }
+}
+
+/// Slack's answer when the object a method is about doesn't exist.
+#[derive(Debug)]
+struct NotFound;
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Not found")
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+fn api_error(error: &str) -> anyhow::Error {
+    match error {
+        "user_not_found" | "channel_not_found" => NotFound.into(),
+        "ratelimited" => anyhow!("Rate limit exceeded"),
+        "invalid_auth" => anyhow!("Invalid authentication"),
+        _ => anyhow!("Slack API error: {}", error),
+    }
+}
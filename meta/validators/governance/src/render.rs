@@ -0,0 +1,317 @@
+use crate::model::{RetrySummary, Span, ValidationReport};
+use crate::rules::Rule;
+use anyhow::Result;
+use clap::ValueEnum;
+use colored::Colorize;
+use serde_json::{Value, json};
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Colored, human-readable summary
+    Text,
+    /// The full validation report as JSON
+    Json,
+    /// SARIF 2.1.0, for GitHub code scanning
+    Sarif,
+    /// GitHub Actions workflow commands, shown as inline annotations
+    Github,
+}
+
+/// Writes `report` to `out`. `rules` describes every rule that may appear in
+/// the report, for formats that carry rule metadata.
+pub fn render(
+    format: Format,
+    report: &ValidationReport,
+    rules: &[Rule],
+    out: &mut dyn Write,
+) -> Result<()> {
+    match format {
+        Format::Text => text(report, out),
+        Format::Json => json(report, out),
+        Format::Sarif => sarif(report, rules, out),
+        Format::Github => github(report, out),
+    }
+}
+
+fn with_location(message: &str, span: Option<Span>) -> String {
+    match span {
+        Some(span) => format!("{} {}", format!("{}:", span).dimmed(), message),
+        None => message.to_string(),
+    }
+}
+
+fn describe_network(network: &RetrySummary) -> String {
+    let mut description = format!(
+        "{} remote request(s), {} retried, {} gave up",
+        network.requests, network.retried, network.gave_up
+    );
+    if network.cached > 0 {
+        description.push_str(&format!(", {} answered from cache", network.cached));
+    }
+    description
+}
+
+fn text(report: &ValidationReport, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "{}", "===== SUMMARY =====".blue().bold())?;
+    writeln!(out, "Contributors: {}", report.stats.contributors_count)?;
+    writeln!(out, "Teams: {}", report.stats.teams_count)?;
+    writeln!(out, "Repos: {}", report.stats.repos_count)?;
+    writeln!(out, "Valid files: {}", report.stats.valid_files_count)?;
+    writeln!(out, "Invalid files: {}", report.stats.invalid_files_count)?;
+    writeln!(out, "Total errors: {}", report.stats.total_errors)?;
+    writeln!(out, "Total warnings: {}", report.stats.total_warnings)?;
+    if !report.network.is_empty() {
+        writeln!(out, "Network: {}", describe_network(&report.network))?;
+    }
+
+    if report.stats.total_errors > 0 {
+        writeln!(out, "\n{}", "===== ERRORS =====".red().bold())?;
+        for (file, messages) in &report.files {
+            if messages.errors.is_empty() {
+                continue;
+            }
+
+            writeln!(out, "{}", file.red().bold())?;
+            for error in &messages.errors {
+                writeln!(out, "  - {}", with_location(&error.message, error.span))?;
+            }
+        }
+    }
+
+    if report.stats.total_warnings > 0 {
+        writeln!(out, "\n{}", "===== WARNINGS =====".yellow().bold())?;
+        for (file, messages) in &report.files {
+            if messages.warnings.is_empty() {
+                continue;
+            }
+
+            writeln!(out, "{}", file.yellow().bold())?;
+            for warning in &messages.warnings {
+                writeln!(out, "  - {}", with_location(&warning.message, warning.span))?;
+            }
+        }
+    }
+
+    if !report.skipped_checks.is_empty() {
+        writeln!(out, "\n{}", "===== SKIPPED CHECKS =====".dimmed().bold())?;
+        for skipped in &report.skipped_checks {
+            writeln!(out, "  - {} ({})", skipped.check, skipped.reason)?;
+        }
+    }
+
+    if report.valid {
+        writeln!(out, "\n{}", "Validation passed!".green().bold())?;
+    } else {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn json(report: &ValidationReport, out: &mut dyn Write) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, report)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn sarif_result(
+    rules: &[Rule],
+    rule: &str,
+    level: &str,
+    file: &str,
+    message: &str,
+    span: Option<Span>,
+) -> Value {
+    // Code scanning needs a line to anchor the annotation to, so findings
+    // without a span are pinned to the top of the file
+    let region = match span {
+        Some(span) => json!({
+            "startLine": span.start.line,
+            "startColumn": span.start.column,
+            "endLine": span.end.line,
+            "endColumn": span.end.column,
+        }),
+        None => json!({ "startLine": 1 }),
+    };
+
+    let mut result = json!({
+        "ruleId": rule,
+        "level": level,
+        "message": { "text": message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": file },
+                "region": region,
+            }
+        }],
+    });
+    if let Some(index) = rules.iter().position(|r| r.id == rule) {
+        result["ruleIndex"] = json!(index);
+    }
+    result
+}
+
+fn sarif(report: &ValidationReport, rules: &[Rule], out: &mut dyn Write) -> Result<()> {
+    let descriptors = rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": rule.default_severity.to_string() },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for (file, messages) in &report.files {
+        for error in &messages.errors {
+            results.push(sarif_result(
+                rules,
+                &error.rule,
+                "error",
+                file,
+                &error.message,
+                error.span,
+            ));
+        }
+        for warning in &messages.warnings {
+            results.push(sarif_result(
+                rules,
+                &warning.rule,
+                "warning",
+                file,
+                &warning.message,
+                warning.span,
+            ));
+        }
+    }
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "governance",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": descriptors,
+                }
+            },
+            // Columns are counted in characters, not SARIF's default of
+            // UTF-16 code units
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+            "invocations": [{
+                "executionSuccessful": true,
+                "toolExecutionNotifications": report
+                    .skipped_checks
+                    .iter()
+                    .map(|skipped| format!("Skipped {}: {}", skipped.check, skipped.reason))
+                    .chain(
+                        (!report.network.is_empty()).then(|| describe_network(&report.network)),
+                    )
+                    .map(|text| json!({
+                        "level": "note",
+                        "message": { "text": text },
+                    }))
+                    .collect::<Vec<_>>(),
+            }],
+        }],
+    });
+
+    serde_json::to_writer_pretty(&mut *out, &log)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Escapes the message of a workflow command.
+pub fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a `key=value` property of a workflow command.
+pub fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn github_command(
+    out: &mut dyn Write,
+    command: &str,
+    rule: &str,
+    file: &str,
+    message: &str,
+    span: Option<Span>,
+) -> Result<()> {
+    let mut properties = format!("file={}", escape_property(file));
+    if let Some(span) = span {
+        properties.push_str(&format!(
+            ",line={},col={},endLine={},endColumn={}",
+            span.start.line, span.start.column, span.end.line, span.end.column
+        ));
+    }
+    properties.push_str(&format!(",title={}", escape_property(rule)));
+
+    writeln!(
+        out,
+        "::{} {}::{}",
+        command,
+        properties,
+        escape_data(message)
+    )?;
+    Ok(())
+}
+
+fn github(report: &ValidationReport, out: &mut dyn Write) -> Result<()> {
+    for (file, messages) in &report.files {
+        if messages.errors.is_empty() && messages.warnings.is_empty() {
+            continue;
+        }
+
+        writeln!(out, "::group::{}", escape_data(file))?;
+        for error in &messages.errors {
+            github_command(out, "error", &error.rule, file, &error.message, error.span)?;
+        }
+        for warning in &messages.warnings {
+            github_command(
+                out,
+                "warning",
+                &warning.rule,
+                file,
+                &warning.message,
+                warning.span,
+            )?;
+        }
+        writeln!(out, "::endgroup::")?;
+    }
+
+    for skipped in &report.skipped_checks {
+        writeln!(
+            out,
+            "::notice title={}::{}",
+            escape_property(&skipped.check),
+            escape_data(&format!("Skipped {}: {}", skipped.check, skipped.reason))
+        )?;
+    }
+
+    if report.network.retried > 0 || report.network.gave_up > 0 {
+        writeln!(
+            out,
+            "::notice title=network::{}",
+            escape_data(&describe_network(&report.network))
+        )?;
+    }
+
+    writeln!(
+        out,
+        "{} error(s) and {} warning(s) across {} contributor(s), {} team(s) and {} repo(s)",
+        report.stats.total_errors,
+        report.stats.total_warnings,
+        report.stats.contributors_count,
+        report.stats.teams_count,
+        report.stats.repos_count
+    )?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::model::Severity;
+
+/// A kind of finding reported by the validator. Rule ids are stable so that
+/// tools consuming the report can key on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub default_severity: Severity,
+}
+
+/// Reported by the loader for files that can't be read or deserialized,
+/// rather than by any individual check.
+pub const INVALID_FILE: Rule = Rule {
+    id: "invalid-file",
+    description: "Governance files must be readable, valid TOML matching their schema",
+    default_severity: Severity::Error,
+};
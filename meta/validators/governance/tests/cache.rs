@@ -0,0 +1,141 @@
+mod common;
+
+use common::{fixture, github, github_rest, slack};
+use governance::checks::{AsyncCheck, GithubUsers, SlackMembers};
+use governance::model::GithubLogin;
+use governance::remote::Cache;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A fresh cache file for one test.
+fn cache_file(name: &str) -> PathBuf {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("{}-cache.json", name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn login(name: &str) -> GithubLogin {
+    name.parse().unwrap()
+}
+
+#[tokio::test]
+async fn confirmed_users_are_not_looked_up_again() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "n0": { "login": "alice" }, "n1": { "login": "bob" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = cache_file("confirmed-users");
+    let cache = Cache::open(&file, DAY);
+    let client = github(&server).with_cache(cache.clone());
+    assert!(
+        GithubUsers::new(client)
+            .run(&fixture("basic"))
+            .await
+            .is_empty()
+    );
+    cache.save().unwrap();
+
+    // A later run reads what the first one saved
+    let cache = Cache::open(&file, DAY);
+    let client = github(&server).with_cache(cache.clone());
+    assert!(
+        GithubUsers::new(client)
+            .run(&fixture("basic"))
+            .await
+            .is_empty()
+    );
+    assert_eq!(cache.hits(), 2);
+}
+
+#[tokio::test]
+async fn missing_users_are_always_looked_up() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/ghost"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let cache = Cache::open(&cache_file("missing-users"), DAY);
+    let client = github_rest(&server).with_cache(cache);
+    for _ in 0..2 {
+        let results = client.users_exist(&[&login("ghost")]).await;
+        assert!(!results[0].as_ref().unwrap());
+    }
+}
+
+#[tokio::test]
+async fn expired_users_are_revalidated_with_their_etag() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/alice"))
+        .and(header("if-none-match", r#""v1""#))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/alice"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", r#""v1""#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // With no TTL, every entry has expired by the next lookup
+    let cache = Cache::open(&cache_file("expired-users"), Duration::ZERO);
+    let client = github(&server).with_cache(cache.clone());
+
+    // The first lookup stores the ETag; the batched one revalidates with it
+    // over REST rather than looking the user up through GraphQL
+    assert!(client.user_exists(&login("alice")).await.unwrap());
+    let results = client.users_exist(&[&login("alice")]).await;
+    assert!(results[0].as_ref().unwrap());
+    assert_eq!(cache.etag("github-user:alice").as_deref(), Some(r#""v1""#));
+}
+
+#[tokio::test]
+async fn confirmed_slack_members_are_not_looked_up_again() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users.info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ok": true })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let cache = Cache::open(&cache_file("slack-members"), DAY);
+    let client = slack(&server).with_cache(cache.clone());
+    for _ in 0..2 {
+        assert!(
+            SlackMembers::new(client.clone())
+                .run(&fixture("basic"))
+                .await
+                .is_empty()
+        );
+    }
+    assert_eq!(cache.hits(), 2);
+}
+
+#[test]
+fn unreadable_caches_start_empty() {
+    let file = cache_file("unreadable");
+    std::fs::write(&file, "not json").unwrap();
+
+    let cache = Cache::open(&file, DAY);
+    assert!(!cache.hit("github-user:alice"));
+    cache.confirm("github-user:alice", None);
+    cache.save().unwrap();
+    assert!(Cache::open(&file, DAY).hit("github-user:alice"));
+}
@@ -0,0 +1,310 @@
+mod common;
+
+use common::{fixture, fixture_root};
+use governance::Governance;
+use governance::checks::{
+    Check, DanglingMembers, DanglingRepos, DuplicateEntries, DuplicateGithubUsers,
+    DuplicateSlackChannels, DuplicateSlackMembers, OrphanContributors, OrphanRepos, Registry,
+    TeamsWithoutChannels, UnsortedLists,
+};
+use governance::config::Config;
+use governance::model::Diagnostic;
+use governance::model::Severity;
+use governance::remote::{Clients, Http, HttpOptions, SlackClient};
+use serde_json::json;
+use wiremock::matchers::{path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Diagnostics as `(file, message)` pairs, in a stable order.
+fn summarize(diagnostics: &[Diagnostic]) -> Vec<(&str, &str)> {
+    let mut summary = diagnostics
+        .iter()
+        .map(|d| (d.file.as_str(), d.message.as_str()))
+        .collect::<Vec<_>>();
+    summary.sort();
+    summary
+}
+
+#[test]
+fn github_usernames_collide_ignoring_case() {
+    let diagnostics = DuplicateGithubUsers.run(&fixture("duplicates"));
+    assert_eq!(
+        summarize(&diagnostics),
+        [
+            (
+                "contributors/alice.toml",
+                "GitHub username 'alice' is also used in contributors/carol.toml"
+            ),
+            (
+                "contributors/carol.toml",
+                "GitHub username 'Alice' is also used in contributors/alice.toml"
+            ),
+        ]
+    );
+    assert!(diagnostics.iter().all(|d| d.span.is_some()));
+}
+
+#[test]
+fn slack_member_ids_collide() {
+    let diagnostics = DuplicateSlackMembers.run(&fixture("duplicates"));
+    assert_eq!(
+        summarize(&diagnostics),
+        [
+            (
+                "contributors/alice.toml",
+                "Slack member ID 'U01ALICE' is also used in contributors/carol.toml"
+            ),
+            (
+                "contributors/carol.toml",
+                "Slack member ID 'U01ALICE' is also used in contributors/alice.toml"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn slack_channels_collide_across_teams() {
+    let diagnostics = DuplicateSlackChannels.run(&fixture("duplicates"));
+    assert_eq!(
+        summarize(&diagnostics),
+        [
+            (
+                "teams/design.toml",
+                "Slack channel ID 'C01SHARED' is also used in teams/web.toml"
+            ),
+            (
+                "teams/web.toml",
+                "Slack channel ID 'C01SHARED' is also used in teams/design.toml"
+            ),
+        ]
+    );
+
+    // Points at the shared entry, not the first one in the list
+    let web = diagnostics
+        .iter()
+        .find(|d| d.file == "teams/web.toml")
+        .unwrap();
+    assert_eq!(web.span.unwrap().start.line, 10);
+}
+
+#[test]
+fn unique_values_pass() {
+    let governance = fixture("basic");
+    assert!(DuplicateGithubUsers.run(&governance).is_empty());
+    assert!(DuplicateSlackMembers.run(&governance).is_empty());
+    assert!(DuplicateSlackChannels.run(&governance).is_empty());
+}
+
+#[test]
+fn repeated_list_entries_are_reported_where_they_repeat() {
+    let diagnostics = DuplicateEntries.run(&fixture("lists"));
+    let found = diagnostics
+        .iter()
+        .map(|d| (d.span.unwrap().start.line, d.message.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            (
+                6,
+                "Team 'web' lists member 'bob' more than once (first at 3:5)"
+            ),
+            (
+                7,
+                "Team 'web' lists member 'Alice', a case variant of 'alice' (first at 4:5)"
+            ),
+            (
+                15,
+                "Team 'web' lists Slack channel 'C01WEB' more than once (first at 14:5)"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unsorted_lists_are_flagged_once_per_list() {
+    let diagnostics = UnsortedLists.run(&fixture("lists"));
+    assert_eq!(
+        summarize(&diagnostics),
+        [(
+            "teams/web.toml",
+            "Team 'web' members are not sorted: 'alice' should come before 'bob'"
+        )]
+    );
+    assert!(DuplicateEntries.run(&fixture("basic")).is_empty());
+    assert!(UnsortedLists.run(&fixture("basic")).is_empty());
+}
+
+#[test]
+fn orphans_are_warnings_by_default() {
+    let governance = fixture("orphans");
+
+    let repos = OrphanRepos.run(&governance);
+    assert_eq!(
+        summarize(&repos),
+        [(
+            "repos/archive.toml",
+            "Repo 'archive' is not owned by any team"
+        )]
+    );
+    assert_eq!(repos[0].severity, Severity::Warning);
+
+    assert_eq!(
+        summarize(&OrphanContributors.run(&governance)),
+        [(
+            "contributors/bob.toml",
+            "Contributor 'bob' is not a member of any team"
+        )]
+    );
+    assert_eq!(
+        summarize(&TeamsWithoutChannels.run(&governance)),
+        [("teams/web.toml", "Team 'web' has no Slack channels")]
+    );
+}
+
+#[tokio::test]
+async fn config_overrides_severity_and_selection() {
+    let root = fixture_root("orphans");
+    let config = Config::load(&root, None).unwrap();
+    assert_eq!(config.file.as_deref(), Some("validator.toml"));
+    let registry = Registry::new(
+        &Clients::from_env("http://unused", "http://unused", HttpOptions::default()),
+        &config,
+    )
+    .select(&config.checks.enable, &config.checks.disable)
+    .unwrap()
+    .with_severities(&config.checks.severity)
+    .unwrap();
+    let (registry, _) = registry.without_unavailable(true);
+
+    let diagnostics = registry.run(&fixture("orphans")).await;
+    let found = diagnostics
+        .iter()
+        .map(|d| (d.rule.as_str(), d.severity))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            ("orphan-repo", Severity::Error),
+            ("orphan-contributor", Severity::Warning),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn raised_severities_leave_failed_lookups_as_warnings() {
+    let server = MockServer::start().await;
+    Mock::given(path("/users.info"))
+        .and(query_param("user", "U01ALICE"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "ok": false, "error": "invalid_auth" })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(path("/users.info"))
+        .and(query_param("user", "U01BOB"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "ok": true,
+            "user": { "id": "U01BOB", "profile": { "real_name": "Robert Jones" } },
+        })))
+        .mount(&server)
+        .await;
+
+    let config: Config = toml::from_str(
+        "[checks]\nenable = [\"slack-identity\"]\n\
+         [checks.severity]\nslack-identity = \"error\"",
+    )
+    .unwrap();
+    // Only Slack answers; the other remote checks fail fast
+    let options = HttpOptions {
+        max_retries: 0,
+        ..HttpOptions::default()
+    };
+    let mut clients = Clients::from_env("http://unused", "http://unused", options);
+    clients.slack = SlackClient::new(
+        Http::new(options),
+        &server.uri(),
+        Some("slack-token".into()),
+    );
+    let registry = Registry::new(&clients, &config)
+        .select(&config.checks.enable, &[])
+        .unwrap()
+        .with_severities(&config.checks.severity)
+        .unwrap();
+
+    let mut found = registry
+        .run(&fixture("basic"))
+        .await
+        .into_iter()
+        .filter(|d| d.rule == "slack-identity")
+        .map(|d| (d.file, d.severity))
+        .collect::<Vec<_>>();
+    found.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        found,
+        [
+            ("contributors/alice.toml".to_string(), Severity::Warning),
+            ("contributors/bob.toml".to_string(), Severity::Error),
+        ]
+    );
+}
+
+#[test]
+fn unknown_checks_in_config_are_rejected() {
+    let config: Config = toml::from_str("[checks.severity]\nno-such-check = \"error\"").unwrap();
+    let registry = Registry::new(
+        &Clients::from_env("http://unused", "http://unused", HttpOptions::default()),
+        &config,
+    );
+    assert!(registry.with_severities(&config.checks.severity).is_err());
+}
+
+#[test]
+fn files_that_fail_to_load_are_only_reported_once() {
+    let files = [
+        (
+            "contributors/a.toml",
+            "full-name = \"A\"\ngithub-username = \"a\"\nslack-member-id = \"not an id\"\n",
+        ),
+        ("repos/r.toml", "name = 1\n"),
+        (
+            "teams/t.toml",
+            "name = \"t\"\nmembers = [\"a\", \"b\"]\nrepos = [\"r\", \"s\"]\nslack-channel-ids = []\n",
+        ),
+        ("teams/u.toml", "name = \"u\"\n"),
+    ]
+    .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()));
+    let governance = Governance::from_files(&files);
+    assert_eq!(governance.load_errors().count(), 3);
+
+    assert_eq!(
+        summarize(&DanglingMembers.run(&governance)),
+        [(
+            "teams/t.toml",
+            "Team 't' references non-existent contributor: b"
+        )]
+    );
+    assert_eq!(
+        summarize(&DanglingRepos.run(&governance)),
+        [("teams/t.toml", "Team 't' references non-existent repo: s")]
+    );
+
+    // Team u failed to load, so it may be what c and q belong to
+    let files = [
+        (
+            "contributors/c.toml",
+            "full-name = \"C\"\ngithub-username = \"c\"\nslack-member-id = \"U01C\"\n",
+        ),
+        ("repos/q.toml", "name = \"q\"\n"),
+        ("teams/u.toml", "name = \"u\"\n"),
+    ]
+    .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()));
+    let governance = Governance::from_files(&files);
+    assert!(OrphanContributors.run(&governance).is_empty());
+    assert!(OrphanRepos.run(&governance).is_empty());
+
+    let governance = Governance::from_files(&files[..2]);
+    assert_eq!(OrphanContributors.run(&governance).len(), 1);
+    assert_eq!(OrphanRepos.run(&governance).len(), 1);
+}
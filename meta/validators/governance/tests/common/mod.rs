@@ -0,0 +1,41 @@
+//! Helpers shared by the integration tests. Each test binary uses its own
+//! subset, so the rest would otherwise warn as dead code there.
+#![allow(dead_code)]
+
+use governance::Governance;
+use governance::remote::{GithubClient, Http, HttpOptions, SlackClient};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use wiremock::MockServer;
+
+pub fn fixture_root(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+pub fn fixture(name: &str) -> Governance {
+    Governance::load(&fixture_root(name)).unwrap()
+}
+
+/// An HTTP layer that retries without making the tests wait.
+pub fn http() -> Http {
+    Http::new(HttpOptions {
+        base_delay: Duration::from_millis(1),
+        ..HttpOptions::default()
+    })
+}
+
+/// A GitHub client for `server`, with a token so that it uses GraphQL.
+pub fn github(server: &MockServer) -> GithubClient {
+    GithubClient::new(http(), &server.uri(), Some("github-token".into()))
+}
+
+/// A tokenless GitHub client, which makes one REST call per lookup.
+pub fn github_rest(server: &MockServer) -> GithubClient {
+    GithubClient::new(http(), &server.uri(), None)
+}
+
+pub fn slack(server: &MockServer) -> SlackClient {
+    SlackClient::new(http(), &server.uri(), Some("slack-token".into()))
+}
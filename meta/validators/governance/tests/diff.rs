@@ -0,0 +1,288 @@
+use governance::Governance;
+use governance::diff::{Change, EntityChange, GovernanceDiff, ListChange};
+use governance::model::{EntityKey, EntityKind, Severity};
+use governance::policy;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn contributor(name: &str) -> (String, String) {
+    (
+        format!("contributors/{}.toml", name),
+        format!(
+            "full-name = \"{0}\"\ngithub-username = \"{0}\"\nslack-member-id = \"U01{1}\"\n",
+            name,
+            name.to_uppercase()
+        ),
+    )
+}
+
+fn team(name: &str, members: &[&str], repos: &[&str]) -> (String, String) {
+    (
+        format!("teams/{}.toml", name),
+        format!(
+            "name = \"{}\"\nmembers = {:?}\nrepos = {:?}\nslack-channel-ids = []\n",
+            name, members, repos
+        ),
+    )
+}
+
+fn repo(name: &str) -> (String, String) {
+    (
+        format!("repos/{}.toml", name),
+        format!("name = \"{}\"\n", name),
+    )
+}
+
+fn load(files: &[(String, String)]) -> Governance {
+    let files = files
+        .iter()
+        .map(|(path, content)| (path.clone(), content.clone().into_bytes()))
+        .collect::<Vec<_>>();
+    Governance::from_files(&files)
+}
+
+fn base() -> Vec<(String, String)> {
+    vec![
+        contributor("alice"),
+        contributor("bob"),
+        team("core", &["alice", "bob"], &["core"]),
+        repo("core"),
+    ]
+}
+
+fn changed(key: EntityKey, change: Change) -> EntityChange {
+    EntityChange { key, change }
+}
+
+fn listed(team: &str, name: &str, change: Change) -> ListChange {
+    ListChange {
+        team: EntityKey::team(team),
+        name: name.to_string(),
+        change,
+    }
+}
+
+/// `(rule, message)` for every diagnostic `author` gets for the change.
+fn check(
+    base: &[(String, String)],
+    head: &[(String, String)],
+    author: &str,
+) -> Vec<(String, String)> {
+    let (base, head) = (load(base), load(head));
+    let diff = GovernanceDiff::between(&base, &head);
+    policy::check_diff(&diff, &base, &head, author)
+        .into_iter()
+        .inspect(|d| assert_eq!(d.severity, Severity::Error))
+        .map(|d| (d.rule, d.message))
+        .collect()
+}
+
+#[test]
+fn entities_are_added_removed_and_modified() {
+    let mut head = base();
+    head[1] = contributor("carol");
+    head[2] = team("core", &["alice"], &["core"]);
+    head[3] = repo("website");
+
+    let diff = GovernanceDiff::between(&load(&base()), &load(&head));
+    assert_eq!(
+        diff.entities,
+        [
+            changed(EntityKey::contributor("bob"), Change::Removed),
+            changed(EntityKey::contributor("carol"), Change::Added),
+            changed(EntityKey::team("core"), Change::Modified),
+            changed(EntityKey::repo("core"), Change::Removed),
+            changed(EntityKey::repo("website"), Change::Added),
+        ]
+    );
+    assert_eq!(diff.members, [listed("core", "bob", Change::Removed)]);
+    assert!(diff.repos.is_empty());
+
+    assert!(GovernanceDiff::between(&load(&base()), &load(&base())).is_empty());
+}
+
+#[test]
+fn memberships_come_and_go_with_their_teams() {
+    let mut head = base();
+    head[2] = team("web", &["bob"], &["website"]);
+
+    let diff = GovernanceDiff::between(&load(&base()), &load(&head));
+    assert_eq!(
+        diff.members,
+        [
+            listed("core", "alice", Change::Removed),
+            listed("core", "bob", Change::Removed),
+            listed("web", "bob", Change::Added),
+        ]
+    );
+    assert_eq!(
+        diff.repos,
+        [
+            listed("core", "core", Change::Removed),
+            listed("web", "website", Change::Added),
+        ]
+    );
+}
+
+#[test]
+fn files_broken_by_a_change_are_modified_not_removed() {
+    let mut head = base();
+    head[2].1 = "name = \"core\"\nmembers = [\n".to_string();
+
+    let diff = GovernanceDiff::between(&load(&base()), &load(&head));
+    assert_eq!(
+        diff.entities,
+        [changed(EntityKey::team("core"), Change::Modified)]
+    );
+    assert!(diff.members.is_empty());
+    assert!(check(&base(), &head, "carol").is_empty());
+}
+
+#[test]
+fn members_may_only_be_removed_by_themselves_or_teammates() {
+    let mut head = base();
+    head[2] = team("core", &["alice"], &["core"]);
+
+    assert!(check(&base(), &head, "bob").is_empty());
+    assert!(check(&base(), &head, "Alice").is_empty());
+    assert_eq!(
+        check(&base(), &head, "carol"),
+        [(
+            "team-member-removal".to_string(),
+            "Only bob or a member of team 'core' may remove them from teams/core.toml, not carol"
+                .to_string()
+        )]
+    );
+
+    // Joining the team in the same change doesn't count
+    head[2] = team("core", &["alice", "carol"], &["core"]);
+    assert_eq!(check(&base(), &head, "carol").len(), 1);
+}
+
+#[test]
+fn additions_are_held_to_the_file_policy() {
+    let mut head = base();
+    head.push(contributor("carol"));
+    head.push(team("web", &["alice"], &["core"]));
+
+    assert_eq!(
+        check(&base(), &head, "alice"),
+        [(
+            "contributor-self-nomination".to_string(),
+            "Contributor file contributors/carol.toml must be submitted by carol themselves, \
+             not by alice"
+                .to_string()
+        )]
+    );
+    assert_eq!(
+        check(&base(), &head, "carol"),
+        [(
+            "team-creator-membership".to_string(),
+            "Team creator carol must be listed as a member in teams/web.toml".to_string()
+        )]
+    );
+}
+
+/// A fresh git repository for one test, with its governance data in `data/`.
+fn repository(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    git(&dir, &["init", "-q"]);
+    dir
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .current_dir(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn write(root: &Path, files: &[(String, String)]) {
+    for dir in ["contributors", "teams", "repos"] {
+        let _ = std::fs::remove_dir_all(root.join(dir));
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    for (path, content) in files {
+        std::fs::write(root.join(path), content).unwrap();
+    }
+}
+
+fn commit(dir: &Path, root: &Path, files: &[(String, String)], message: &str) {
+    write(root, files);
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-q", "-m", message]);
+}
+
+#[test]
+fn revisions_are_read_from_git_history() {
+    let dir = repository("diff-history");
+    let root = dir.join("data");
+
+    commit(&dir, &root, &base(), "base");
+    let mut head = base();
+    head[2] = team("core", &["alice"], &["core"]);
+    commit(&dir, &root, &head, "head");
+
+    // The working tree doesn't matter
+    write(&root, &[contributor("carol")]);
+
+    let before = Governance::load_revision(&root, "HEAD~1").unwrap();
+    let after = Governance::load_revision(&root, "HEAD").unwrap();
+    assert_eq!(before.count(EntityKind::Contributor), 2);
+    assert!(after.contributor("carol").is_none());
+
+    let diff = GovernanceDiff::between(&before, &after);
+    assert_eq!(
+        diff.entities,
+        [changed(EntityKey::team("core"), Change::Modified)]
+    );
+    assert_eq!(diff.members, [listed("core", "bob", Change::Removed)]);
+    // Spans point into the file as of the revision
+    assert_eq!(
+        after
+            .field_span(&EntityKey::team("core"), "members")
+            .map(|span| span.start.line),
+        Some(2)
+    );
+}
+
+#[test]
+fn unknown_revisions_are_errors() {
+    let dir = repository("diff-unknown");
+    let root = dir.join("data");
+    commit(&dir, &root, &base(), "base");
+
+    let error = Governance::load_revision(&root, "no-such-branch").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Failed to read the governance data at no-such-branch"
+    );
+    assert!(Governance::load_revision(&root, "--output=x").is_err());
+}
+
+#[test]
+fn bots_may_only_make_changes_no_rule_covers() {
+    let mut head = base();
+    head[2] = team("core", &["alice"], &["core", "website"]);
+    head.push(repo("website"));
+    assert_eq!(
+        check(&base(), &head, "github-actions[bot]"),
+        [(
+            "team-member-removal".to_string(),
+            "Only bob or a member of team 'core' may remove them from teams/core.toml, \
+             not github-actions[bot]"
+                .to_string()
+        )]
+    );
+
+    let mut head = base();
+    head[2] = team("core", &["alice", "bob"], &["core", "website"]);
+    head.push(repo("website"));
+    assert!(check(&base(), &head, "github-actions[bot]").is_empty());
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::fixture;
+use governance::checks::{Check, DanglingRepos, FileNames};
+use governance::model::{Entity, EntityKey, EntityKind};
+
+#[test]
+fn keys_of_different_kinds_do_not_collide() {
+    assert_ne!(EntityKey::team("core"), EntityKey::repo("core"));
+    assert_eq!(EntityKey::team("core").scoped_id(), "team:core");
+    assert_eq!(EntityKey::repo("core").file(), "repos/core.toml");
+}
+
+#[test]
+fn team_and_repo_with_the_same_name_coexist() {
+    let governance = fixture("basic");
+
+    assert!(matches!(
+        governance.get(&EntityKey::team("core")),
+        Some(Entity::Team(_))
+    ));
+    assert!(matches!(
+        governance.get(&EntityKey::repo("core")),
+        Some(Entity::Repo(_))
+    ));
+    assert!(governance.contributor("core").is_none());
+
+    assert_eq!(governance.count(EntityKind::Contributor), 2);
+    assert_eq!(governance.count(EntityKind::Team), 1);
+    assert_eq!(governance.count(EntityKind::Repo), 2);
+    assert_eq!(governance.load_errors().count(), 0);
+}
+
+#[test]
+fn fixture_passes_local_checks() {
+    let governance = fixture("basic");
+    assert!(FileNames.run(&governance).is_empty());
+    assert!(DanglingRepos.run(&governance).is_empty());
+}
+
+#[test]
+fn membership_queries_follow_teams() {
+    let governance = fixture("basic");
+
+    assert_eq!(
+        governance.teams_of("alice").collect::<Vec<_>>(),
+        [&EntityKey::team("core")]
+    );
+    assert_eq!(
+        governance.owners_of("website").collect::<Vec<_>>(),
+        [&EntityKey::team("core")]
+    );
+    assert_eq!(
+        governance.repos_of("bob").into_iter().collect::<Vec<_>>(),
+        [EntityKey::repo("core"), EntityKey::repo("website")]
+    );
+    assert_eq!(
+        governance
+            .members_of("core")
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [
+            EntityKey::contributor("alice"),
+            EntityKey::contributor("bob")
+        ]
+    );
+
+    assert_eq!(governance.teams_of("nobody").count(), 0);
+    assert!(governance.members_of("nothing").is_empty());
+}
@@ -0,0 +1,378 @@
+mod common;
+
+use common::{fixture, github, http};
+use governance::checks::{AsyncCheck, GithubRepos, GithubUsers, OrgMembers};
+use governance::model::{GithubLogin, RetrySummary, Severity};
+use governance::remote::{GRAPHQL_BATCH_SIZE, GithubClient};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use wiremock::matchers::{body_partial_json, body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// The `(alias, last string argument)` of every lookup in a batched query,
+/// e.g. `("n0", "alice")` for `n0: user(login: "alice") { login }`.
+fn lookups(query: &str) -> Vec<(String, String)> {
+    query
+        .split(") {")
+        .filter_map(|part| {
+            let (head, args) = part.rsplit_once('(')?;
+            let alias = head.rsplit_once(": ")?.0.rsplit(' ').next()?;
+            let name = args.rsplit('"').nth(1)?;
+            Some((alias.to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+/// Answers batched lookups from a fixed set of nodes, keyed by the looked-up
+/// name, reporting anything else as not found the way GitHub does.
+struct Nodes(HashMap<String, Value>);
+
+impl Nodes {
+    fn new<const N: usize>(nodes: [(&str, Value); N]) -> Self {
+        Self(
+            nodes
+                .into_iter()
+                .map(|(name, node)| (name.to_string(), node))
+                .collect(),
+        )
+    }
+}
+
+impl Respond for Nodes {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        let mut data = Map::new();
+        let mut errors = Vec::new();
+
+        for (alias, name) in lookups(body["query"].as_str().unwrap()) {
+            match self.0.get(&name) {
+                Some(node) => {
+                    data.insert(alias, node.clone());
+                }
+                None => {
+                    errors.push(json!({
+                        "type": "NOT_FOUND",
+                        "path": [alias],
+                        "message": format!("Could not resolve to a node with the name of '{}'.", name),
+                    }));
+                    data.insert(alias, Value::Null);
+                }
+            }
+        }
+
+        ResponseTemplate::new(200).set_body_json(json!({ "data": data, "errors": errors }))
+    }
+}
+
+async fn graphql(server: &MockServer, responder: impl Respond + 'static) {
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(header("authorization", "Bearer github-token"))
+        .respond_with(responder)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn users_are_resolved_in_one_query() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .respond_with(Nodes::new([
+            ("alice", json!({ "login": "alice" })),
+            ("bob", json!({ "login": "bob" })),
+        ]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let diagnostics = GithubUsers::new(github(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[tokio::test]
+async fn missing_users_are_errors() {
+    let server = MockServer::start().await;
+    graphql(
+        &server,
+        Nodes::new([("alice", json!({ "login": "alice" }))]),
+    )
+    .await;
+
+    let diagnostics = GithubUsers::new(github(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].file, "contributors/bob.toml");
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[tokio::test]
+async fn failed_queries_are_inconclusive() {
+    let server = MockServer::start().await;
+    graphql(
+        &server,
+        ResponseTemplate::new(200).set_body_json(json!({
+            "errors": [{ "type": "RATE_LIMITED", "message": "API rate limit exceeded" }]
+        })),
+    )
+    .await;
+
+    let diagnostics = GithubUsers::new(github(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(diagnostics.len(), 2);
+    for diagnostic in &diagnostics {
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert!(diagnostic.message.contains("API rate limit exceeded"));
+    }
+}
+
+#[tokio::test]
+async fn rate_limited_queries_wait_for_the_reset_and_retry() {
+    let server = MockServer::start().await;
+    let reset = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 1;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", reset.to_string().as_str())
+                .set_body_json(json!({
+                    "errors": [{ "type": "RATE_LIMITED", "message": "API rate limit exceeded" }]
+                })),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    graphql(
+        &server,
+        Nodes::new([
+            ("alice", json!({ "login": "alice" })),
+            ("bob", json!({ "login": "bob" })),
+        ]),
+    )
+    .await;
+
+    let http = http();
+    let client = GithubClient::new(http.clone(), &server.uri(), Some("github-token".into()));
+    let started = Instant::now();
+    let diagnostics = GithubUsers::new(client).run(&fixture("basic")).await;
+
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(
+        http.summary(),
+        RetrySummary {
+            requests: 1,
+            retried: 1,
+            gave_up: 0,
+            cached: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn lookups_are_split_into_batches() {
+    let server = MockServer::start().await;
+    let logins = (0..GRAPHQL_BATCH_SIZE + 50)
+        .map(|i| format!("user-{}", i).parse::<GithubLogin>().unwrap())
+        .collect::<Vec<_>>();
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .respond_with(Nodes::new([("user-7", json!({ "login": "user-7" }))]))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let results = github(&server)
+        .users_exist(&logins.iter().collect::<Vec<_>>())
+        .await;
+    let found = results
+        .into_iter()
+        .enumerate()
+        .filter(|(_, result)| *result.as_ref().unwrap())
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    assert_eq!(found, [7]);
+}
+
+#[tokio::test]
+async fn repos_report_missing_and_renamed() {
+    let server = MockServer::start().await;
+    graphql(
+        &server,
+        Nodes::new([("website", json!({ "name": "homepage", "isArchived": true }))]),
+    )
+    .await;
+
+    let check = GithubRepos::new(github(&server), Some("acme".into()));
+    let mut messages = check
+        .run(&fixture("basic"))
+        .await
+        .into_iter()
+        .map(|d| d.message)
+        .collect::<Vec<_>>();
+    messages.sort();
+    assert_eq!(
+        messages,
+        [
+            "Repo acme/core does not exist",
+            "Repo acme/website has been renamed to acme/homepage",
+        ]
+    );
+}
+
+/// One page of the `connection` of users in the org `acme`.
+async fn roster_page(
+    server: &MockServer,
+    connection: &str,
+    after: Value,
+    logins: &[&str],
+    next: Option<&str>,
+) {
+    let nodes = logins
+        .iter()
+        .map(|login| json!({ "login": login }))
+        .collect::<Vec<_>>();
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains(connection))
+        .and(body_partial_json(
+            json!({ "variables": { "org": "acme", "after": after } }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "organization": { connection: {
+                "nodes": nodes,
+                "pageInfo": { "hasNextPage": next.is_some(), "endCursor": next },
+            } } }
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn org_membership_is_read_from_one_paged_roster() {
+    let server = MockServer::start().await;
+    roster_page(
+        &server,
+        "membersWithRole",
+        Value::Null,
+        &["carol"],
+        Some("c1"),
+    )
+    .await;
+    roster_page(&server, "membersWithRole", json!("c1"), &["Dave"], None).await;
+    roster_page(&server, "pendingMembers", Value::Null, &["Alice"], None).await;
+    // Only logins the roster doesn't show are asked about on their own
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/memberships/bob"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let check = OrgMembers::new(github(&server), Some("acme".into()));
+    let mut diagnostics = check.run(&fixture("basic")).await;
+    diagnostics.sort_by(|a, b| a.file.cmp(&b.file));
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| (d.severity, d.message.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (
+                Severity::Warning,
+                "GitHub user alice has not accepted the invitation to the acme org"
+            ),
+            (
+                Severity::Warning,
+                "GitHub user bob is not a member of the acme org"
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn org_membership_without_admin_rights_falls_back_to_each_user() {
+    let server = MockServer::start().await;
+    roster_page(&server, "membersWithRole", Value::Null, &["carol"], None).await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("pendingMembers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "organization": { "pendingMembers": null } },
+            "errors": [{
+                "type": "FORBIDDEN",
+                "path": ["organization", "pendingMembers"],
+                "message": "Must have admin rights to Organization.",
+            }],
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/memberships/alice"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "state": "pending" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/memberships/bob"))
+        .respond_with(ResponseTemplate::new(403))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let check = OrgMembers::new(github(&server), Some("acme".into()));
+    let mut diagnostics = check.run(&fixture("basic")).await;
+    diagnostics.sort_by(|a, b| a.file.cmp(&b.file));
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| (d.inconclusive, d.message.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (
+                false,
+                "GitHub user alice has not accepted the invitation to the acme org"
+            ),
+            (
+                true,
+                "Failed to check acme org membership of bob: Rate limit exceeded or access forbidden"
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn unknown_orgs_are_inconclusive() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "organization": null },
+            "errors": [{
+                "type": "NOT_FOUND",
+                "path": ["organization"],
+                "message": "Could not resolve to an Organization with the login of 'acme'.",
+            }],
+        })))
+        .mount(&server)
+        .await;
+
+    let check = OrgMembers::new(github(&server), Some("acme".into()));
+    let diagnostics = check.run(&fixture("basic")).await;
+    assert_eq!(diagnostics.len(), 2);
+    for diagnostic in diagnostics {
+        assert!(diagnostic.inconclusive);
+        assert!(diagnostic.message.contains("Could not resolve"));
+    }
+}
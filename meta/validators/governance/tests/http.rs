@@ -0,0 +1,144 @@
+use governance::model::RetrySummary;
+use governance::remote::{Http, HttpOptions};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn http(options: HttpOptions) -> Http {
+    Http::new(HttpOptions {
+        base_delay: Duration::from_millis(1),
+        ..options
+    })
+}
+
+async fn get(http: &Http, server: &MockServer) -> anyhow::Result<reqwest::Response> {
+    http.send(http.get(&format!("{}/thing", server.uri())))
+        .await
+}
+
+#[tokio::test]
+async fn rate_limited_requests_are_retried_after_the_requested_delay() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/thing"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/thing"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let http = http(HttpOptions::default());
+    let started = Instant::now();
+    let response = get(&http, &server).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(
+        http.summary(),
+        RetrySummary {
+            requests: 1,
+            retried: 1,
+            gave_up: 0,
+            cached: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn persistent_failures_give_up_after_max_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/thing"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let http = http(HttpOptions {
+        max_retries: 2,
+        ..HttpOptions::default()
+    });
+    let response = get(&http, &server).await.unwrap();
+
+    // The last response is handed back for the caller to report
+    assert_eq!(response.status(), 503);
+    assert_eq!(http.summary().gave_up, 1);
+}
+
+#[tokio::test]
+async fn distant_rate_limit_resets_are_not_waited_out() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/thing"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "99999999999"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let http = http(HttpOptions::default());
+    let response = get(&http, &server).await.unwrap();
+    assert_eq!(response.status(), 403);
+    assert_eq!(http.summary().gave_up, 1);
+}
+
+#[tokio::test]
+async fn forbidden_without_rate_limit_headers_is_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/thing"))
+        .respond_with(ResponseTemplate::new(403).insert_header("x-ratelimit-remaining", "42"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let http = http(HttpOptions::default());
+    assert_eq!(get(&http, &server).await.unwrap().status(), 403);
+    assert_eq!(http.summary().retried, 0);
+}
+
+#[tokio::test]
+async fn slow_requests_time_out_and_are_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/thing"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let http = http(HttpOptions {
+        timeout: Duration::from_millis(100),
+        max_retries: 1,
+        ..HttpOptions::default()
+    });
+    let error = get(&http, &server).await.unwrap_err();
+    assert!(error.to_string().contains("Gave up after 2 attempt(s)"));
+}
+
+#[tokio::test]
+async fn concurrency_is_capped() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/thing"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(200)))
+        .mount(&server)
+        .await;
+
+    let http = http(HttpOptions {
+        max_concurrency: 1,
+        ..HttpOptions::default()
+    });
+    let started = Instant::now();
+    let responses = futures::future::join_all((0..3).map(|_| get(&http, &server))).await;
+
+    assert!(responses.iter().all(|r| r.is_ok()));
+    assert!(started.elapsed() >= Duration::from_millis(600));
+}
@@ -0,0 +1,49 @@
+use governance::model::{Contributor, GithubLogin, SlackChannelId, SlackUserId};
+
+#[test]
+fn github_logins_follow_github_rules() {
+    for login in ["ap-1", "Yuxiang-Huang", "a", &"a".repeat(39)] {
+        assert!(login.parse::<GithubLogin>().is_ok(), "{}", login);
+    }
+    for login in ["", "-ap", "ap-", "a--b", "a_b", "a.b", &"a".repeat(40)] {
+        assert!(login.parse::<GithubLogin>().is_err(), "{}", login);
+    }
+}
+
+#[test]
+fn slack_ids_are_told_apart() {
+    assert!("U07FPJKFB5E".parse::<SlackUserId>().is_ok());
+    // Enterprise Grid member IDs
+    assert!("W012A3CDE".parse::<SlackUserId>().is_ok());
+    assert!("C08K3Q77ZQF".parse::<SlackChannelId>().is_ok());
+    assert!("G08K3Q77ZQF".parse::<SlackChannelId>().is_ok());
+
+    let error = "C08K3Q77ZQF".parse::<SlackUserId>().unwrap_err();
+    assert!(error.contains("found a channel ID"), "{}", error);
+
+    let error = "U07FPJKFB5E".parse::<SlackChannelId>().unwrap_err();
+    assert!(error.contains("found a member ID"), "{}", error);
+    let error = "W012A3CDE".parse::<SlackChannelId>().unwrap_err();
+    assert!(error.contains("found a member ID"), "{}", error);
+
+    let error = "T012AB3C4".parse::<SlackUserId>().unwrap_err();
+    assert!(error.contains("found a workspace ID"), "{}", error);
+
+    assert!("u07fpjkfb5e".parse::<SlackUserId>().is_err());
+    assert!("U".parse::<SlackUserId>().is_err());
+}
+
+#[test]
+fn malformed_ids_fail_to_deserialize() {
+    let error = toml::from_str::<Contributor>(
+        r#"
+        full-name = "Someone"
+        github-username = "someone"
+        slack-member-id = "C08K3Q77ZQF"
+        "#,
+    )
+    .unwrap_err();
+
+    assert!(error.message().contains("not a Slack member ID"));
+    assert!(error.span().is_some());
+}
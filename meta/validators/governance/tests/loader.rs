@@ -0,0 +1,170 @@
+use governance::Governance;
+use governance::loader::{find_root, parse_file, resolve_root};
+use governance::model::{EntityKind, SourceSpans, Span, Team};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `(start line, start column, end line, end column)`
+fn bounds(span: Option<Span>) -> Option<(usize, usize, usize, usize)> {
+    span.map(|s| (s.start.line, s.start.column, s.end.line, s.end.column))
+}
+
+fn team(content: &str) -> SourceSpans {
+    parse_file::<Team>("teams/core.toml".into(), content, EntityKind::Team)
+        .unwrap()
+        .1
+}
+
+/// An empty directory for one test.
+fn scratch(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A governance root under `dir` with the given files.
+fn root_with(dir: &Path, files: &[(&str, &[u8])]) {
+    for kind in [EntityKind::Contributor, EntityKind::Team, EntityKind::Repo] {
+        fs::create_dir_all(dir.join(kind.dir())).unwrap();
+    }
+    for (path, content) in files {
+        fs::write(dir.join(path), content).unwrap();
+    }
+}
+
+#[test]
+fn spans_cover_multi_line_arrays_and_their_items() {
+    let spans = team(
+        "name = \"core\"\n\
+         members = [\n    \"alice\",\n    \"bob\",\n]\n\
+         repos = []\nslack-channel-ids = []\n",
+    );
+    assert_eq!(bounds(spans.field("name")), Some((1, 8, 1, 14)));
+    assert_eq!(bounds(spans.field("members")), Some((2, 11, 5, 2)));
+    assert_eq!(bounds(spans.item("members", 0)), Some((3, 5, 3, 12)));
+    assert_eq!(bounds(spans.item("members", 1)), Some((4, 5, 4, 10)));
+    assert_eq!(spans.item("members", 2), None);
+}
+
+#[test]
+fn columns_count_characters_not_bytes() {
+    let spans = team(
+        "name = \"Zoë's team\" # ü\n\
+         members = [\"zoë\", \"bob\"]\nrepos = []\nslack-channel-ids = []\n",
+    );
+    assert_eq!(bounds(spans.item("members", 1)), Some((2, 19, 2, 24)));
+    assert_eq!(bounds(spans.field("repos")), Some((3, 9, 3, 11)));
+}
+
+#[test]
+fn crlf_line_endings_give_the_same_positions() {
+    let spans = team(
+        "name = \"core\"\r\nmembers = [\r\n    \"alice\",\r\n    \"bob\",\r\n]\r\n\
+         repos = []\r\nslack-channel-ids = []\r\n",
+    );
+    assert_eq!(bounds(spans.field("members")), Some((2, 11, 5, 2)));
+    assert_eq!(bounds(spans.item("members", 1)), Some((4, 5, 4, 10)));
+    assert_eq!(
+        bounds(spans.field("slack-channel-ids")),
+        Some((7, 21, 7, 23))
+    );
+}
+
+#[test]
+fn parse_errors_point_at_the_problem() {
+    let error = parse_file::<Team>(
+        "teams/core.toml".into(),
+        "name = \"core\"\nmembers = [\n    \"alice\"\n    \"bob\",\n]\n",
+        EntityKind::Team,
+    )
+    .unwrap_err();
+    assert_eq!(error.rule, "invalid-file");
+    assert_eq!(error.file, "teams/core.toml");
+    assert!(error.message.starts_with("Failed to parse team file: "));
+    assert_eq!(error.span.map(|span| span.start.line), Some(4));
+}
+
+#[test]
+fn the_root_is_found_from_anywhere_inside_it() {
+    let root = scratch("loader-root");
+    root_with(&root, &[]);
+    let nested = root.join("teams/deeper");
+    fs::create_dir_all(&nested).unwrap();
+
+    assert_eq!(find_root(&nested), Some(root.clone()));
+    assert_eq!(find_root(&root), Some(root.clone()));
+    assert_eq!(resolve_root(Some(&root)).unwrap(), root);
+
+    // Only a directory with all three data directories is a root. The search
+    // carries on upwards, and the target directory sits inside this repo.
+    fs::remove_dir_all(root.join("repos")).unwrap();
+    assert_ne!(find_root(&nested), Some(root.clone()));
+    let error = resolve_root(Some(&root)).unwrap_err();
+    assert!(
+        error.to_string().ends_with(
+            "is not a governance root (expected contributors/, teams/ and repos/ directories)"
+        ),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn every_file_that_fails_to_load_is_reported() {
+    let root = scratch("loader-errors");
+    root_with(
+        &root,
+        &[
+            (
+                "contributors/good.toml",
+                b"full-name = \"Good\"\ngithub-username = \"good\"\nslack-member-id = \"U01GOOD\"\n",
+            ),
+            ("contributors/syntax.toml", b"full-name = \"Bad\n"),
+            ("contributors/schema.toml", b"full-name = \"Bad\"\n"),
+            ("contributors/binary.toml", b"full-name = \"\xff\"\n"),
+            ("repos/broken.toml", b"name = 1\n"),
+        ],
+    );
+
+    let governance = Governance::load(&root).unwrap();
+    assert!(governance.contributor("good").is_some());
+    assert_eq!(governance.count(EntityKind::Contributor), 1);
+
+    let mut errors = governance
+        .load_errors()
+        .map(|e| {
+            (
+                e.file.as_str(),
+                e.rule.as_str(),
+                e.message.split(':').next().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    errors.sort();
+    assert_eq!(
+        errors,
+        [
+            (
+                "contributors/binary.toml",
+                "invalid-file",
+                "Failed to read contributor file"
+            ),
+            (
+                "contributors/schema.toml",
+                "invalid-file",
+                "Failed to parse contributor file"
+            ),
+            (
+                "contributors/syntax.toml",
+                "invalid-file",
+                "Failed to parse contributor file"
+            ),
+            (
+                "repos/broken.toml",
+                "invalid-file",
+                "Failed to parse repo file"
+            ),
+        ]
+    );
+}
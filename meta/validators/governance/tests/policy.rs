@@ -0,0 +1,106 @@
+mod common;
+
+use common::fixture_root;
+use governance::model::Severity;
+use governance::policy::{self, FilePolicy};
+
+fn check(root: &str, author: &str, added: &[&str]) -> Vec<FilePolicy> {
+    policy::check(&fixture_root(root), author, added)
+}
+
+/// `(file, rule)` for every diagnostic.
+fn summarize(checked: &[FilePolicy]) -> Vec<(&str, &str)> {
+    checked
+        .iter()
+        .flat_map(|file| &file.diagnostics)
+        .map(|d| (d.file.as_str(), d.rule.as_str()))
+        .collect()
+}
+
+#[test]
+fn contributors_must_nominate_themselves() {
+    let checked = check("basic", "alice", &["contributors/alice.toml"]);
+    assert_eq!(checked.len(), 1);
+    assert!(summarize(&checked).is_empty());
+
+    // GitHub logins are case-insensitive
+    assert!(summarize(&check("basic", "Alice", &["./contributors/alice.toml"])).is_empty());
+
+    let checked = check("basic", "bob", &["contributors/alice.toml"]);
+    let diagnostic = &checked[0].diagnostics[0];
+    assert_eq!(diagnostic.rule, "contributor-self-nomination");
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(
+        diagnostic.message,
+        "Contributor file contributors/alice.toml must be submitted by alice themselves, not by bob"
+    );
+    assert_eq!(diagnostic.span.map(|span| span.start.line), Some(2));
+}
+
+#[test]
+fn team_creators_must_be_members() {
+    assert!(summarize(&check("basic", "bob", &["teams/core.toml"])).is_empty());
+
+    let checked = check("basic", "carol", &["teams/core.toml"]);
+    assert_eq!(
+        summarize(&checked),
+        [("teams/core.toml", "team-creator-membership")]
+    );
+    assert_eq!(
+        checked[0].diagnostics[0].message,
+        "Team creator carol must be listed as a member in teams/core.toml"
+    );
+}
+
+#[test]
+fn team_creators_named_outside_members_are_not_members() {
+    // The author appears in the file, but as a repo
+    assert_eq!(
+        summarize(&check("policy", "carol", &["teams/web.toml"])),
+        [("teams/web.toml", "team-creator-membership")]
+    );
+}
+
+#[test]
+fn unparseable_files_fail_the_policy() {
+    assert_eq!(
+        summarize(&check("policy", "broken", &["contributors/broken.toml"])),
+        [("contributors/broken.toml", "invalid-file")]
+    );
+}
+
+#[test]
+fn other_files_are_left_alone() {
+    let checked = check(
+        "basic",
+        "carol",
+        &[
+            "README.md",
+            "repos/core.toml",
+            "teams/notes.txt",
+            "meta/teams/core.toml",
+            "../basic/teams/core.toml",
+        ],
+    );
+    assert!(checked.is_empty(), "{:?}", checked);
+}
+
+#[test]
+fn bots_are_held_to_the_same_rules() {
+    assert_eq!(
+        summarize(&check(
+            "basic",
+            "dependabot[bot]",
+            &[
+                "contributors/alice.toml",
+                "teams/core.toml",
+                "repos/core.toml"
+            ]
+        )),
+        [
+            ("contributors/alice.toml", "contributor-self-nomination"),
+            ("teams/core.toml", "team-creator-membership"),
+        ]
+    );
+    assert!(summarize(&check("basic", "dependabot[bot]", &["repos/core.toml"])).is_empty());
+}
@@ -0,0 +1,363 @@
+mod common;
+
+use common::{fixture, github, github_rest, http, slack};
+use governance::Governance;
+use governance::checks::{
+    AsyncCheck, GithubRepos, GithubUsers, SlackChannels, SlackMembers, UngovernedRepos,
+};
+use governance::model::{Diagnostic, Severity};
+use governance::remote::GithubClient;
+use serde_json::json;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Diagnostics as `(severity, file)` pairs, in a stable order.
+fn summarize(diagnostics: &[Diagnostic]) -> Vec<(Severity, &str)> {
+    let mut summary = diagnostics
+        .iter()
+        .map(|d| (d.severity, d.file.as_str()))
+        .collect::<Vec<_>>();
+    summary.sort_by_key(|&(_, file)| file);
+    summary
+}
+
+async fn github_user(server: &MockServer, login: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path(format!("/users/{}", login)))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+async fn slack_call(
+    server: &MockServer,
+    api: &str,
+    param: (&str, &str),
+    response: ResponseTemplate,
+) {
+    Mock::given(method("GET"))
+        .and(path(format!("/{}", api)))
+        .and(query_param(param.0, param.1))
+        .and(header("authorization", "Bearer slack-token"))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+fn slack_ok() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({ "ok": true }))
+}
+
+fn slack_error(error: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({ "ok": false, "error": error }))
+}
+
+#[tokio::test]
+async fn github_existing_users_pass() {
+    let server = MockServer::start().await;
+    github_user(&server, "alice", ResponseTemplate::new(200)).await;
+    github_user(&server, "bob", ResponseTemplate::new(200)).await;
+
+    // Without a token, users are looked up anonymously rather than skipped
+    let check = GithubUsers::new(github_rest(&server));
+    assert!(check.unavailable().is_none());
+    let diagnostics = check.run(&fixture("basic")).await;
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[tokio::test]
+async fn github_missing_user_is_an_error() {
+    let server = MockServer::start().await;
+    github_user(&server, "alice", ResponseTemplate::new(200)).await;
+    github_user(&server, "bob", ResponseTemplate::new(404)).await;
+
+    let diagnostics = GithubUsers::new(github_rest(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(&diagnostics),
+        [(Severity::Error, "contributors/bob.toml")]
+    );
+    assert_eq!(diagnostics[0].rule, "unknown-github-user");
+    assert!(diagnostics[0].span.is_some());
+}
+
+#[tokio::test]
+async fn github_forbidden_is_inconclusive() {
+    let server = MockServer::start().await;
+    github_user(&server, "alice", ResponseTemplate::new(403)).await;
+    github_user(&server, "bob", ResponseTemplate::new(500)).await;
+
+    let diagnostics = GithubUsers::new(github_rest(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(&diagnostics),
+        [
+            (Severity::Warning, "contributors/alice.toml"),
+            (Severity::Warning, "contributors/bob.toml"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn slack_existing_members_pass() {
+    let server = MockServer::start().await;
+    slack_call(&server, "users.info", ("user", "U01ALICE"), slack_ok()).await;
+    slack_call(&server, "users.info", ("user", "U01BOB"), slack_ok()).await;
+
+    let diagnostics = SlackMembers::new(slack(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[tokio::test]
+async fn slack_not_found_is_an_error() {
+    let server = MockServer::start().await;
+    slack_call(&server, "users.info", ("user", "U01ALICE"), slack_ok()).await;
+    slack_call(
+        &server,
+        "users.info",
+        ("user", "U01BOB"),
+        slack_error("user_not_found"),
+    )
+    .await;
+    slack_call(
+        &server,
+        "conversations.info",
+        ("channel", "C01CORE"),
+        slack_error("channel_not_found"),
+    )
+    .await;
+
+    let governance = fixture("basic");
+    let members = SlackMembers::new(slack(&server)).run(&governance).await;
+    assert_eq!(
+        summarize(&members),
+        [(Severity::Error, "contributors/bob.toml")]
+    );
+
+    let channels = SlackChannels::new(slack(&server)).run(&governance).await;
+    assert_eq!(summarize(&channels), [(Severity::Error, "teams/core.toml")]);
+    assert_eq!(channels[0].rule, "unknown-slack-channel");
+}
+
+#[tokio::test]
+async fn slack_api_errors_are_inconclusive() {
+    let server = MockServer::start().await;
+    slack_call(
+        &server,
+        "users.info",
+        ("user", "U01ALICE"),
+        slack_error("ratelimited"),
+    )
+    .await;
+    slack_call(
+        &server,
+        "users.info",
+        ("user", "U01BOB"),
+        slack_error("invalid_auth"),
+    )
+    .await;
+
+    let diagnostics = SlackMembers::new(slack(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(&diagnostics),
+        [
+            (Severity::Warning, "contributors/alice.toml"),
+            (Severity::Warning, "contributors/bob.toml"),
+        ]
+    );
+    assert!(diagnostics.iter().any(|d| d.message.contains("Rate limit")));
+    assert!(
+        diagnostics
+            .iter()
+            .any(|d| d.message.contains("authentication"))
+    );
+}
+
+#[tokio::test]
+async fn slack_malformed_json_is_inconclusive() {
+    let server = MockServer::start().await;
+    slack_call(
+        &server,
+        "conversations.info",
+        ("channel", "C01CORE"),
+        ResponseTemplate::new(200).set_body_string("<html>not json</html>"),
+    )
+    .await;
+
+    let diagnostics = SlackChannels::new(slack(&server))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(&diagnostics),
+        [(Severity::Warning, "teams/core.toml")]
+    );
+}
+
+async fn github_repo(server: &MockServer, name: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path(format!("/repos/acme/{}", name)))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+fn repo_json(name: &str, archived: bool) -> serde_json::Value {
+    json!({ "name": name, "archived": archived })
+}
+
+#[tokio::test]
+async fn github_repos_report_missing_renamed_and_archived() {
+    let server = MockServer::start().await;
+    github_repo(&server, "core", ResponseTemplate::new(404)).await;
+    github_repo(
+        &server,
+        "website",
+        ResponseTemplate::new(200).set_body_json(repo_json("homepage", false)),
+    )
+    .await;
+
+    let check = GithubRepos::new(github_rest(&server), Some("acme".into()));
+    let diagnostics = check.run(&fixture("basic")).await;
+    let mut messages = diagnostics
+        .iter()
+        .map(|d| (d.severity, d.message.as_str()))
+        .collect::<Vec<_>>();
+    messages.sort_by_key(|&(_, message)| message);
+    assert_eq!(
+        messages,
+        [
+            (Severity::Error, "Repo acme/core does not exist"),
+            (
+                Severity::Error,
+                "Repo acme/website has been renamed to acme/homepage"
+            ),
+        ]
+    );
+
+    let server = MockServer::start().await;
+    github_repo(
+        &server,
+        "core",
+        ResponseTemplate::new(200).set_body_json(repo_json("Core", true)),
+    )
+    .await;
+    github_repo(
+        &server,
+        "website",
+        ResponseTemplate::new(200).set_body_json(repo_json("website", false)),
+    )
+    .await;
+
+    let check = GithubRepos::new(github_rest(&server), Some("acme".into()));
+    let diagnostics = check.run(&fixture("basic")).await;
+    assert_eq!(
+        summarize(&diagnostics),
+        [(Severity::Warning, "repos/core.toml")]
+    );
+    assert_eq!(diagnostics[0].message, "Repo acme/core is archived");
+}
+
+#[tokio::test]
+async fn ungoverned_repos_are_found_across_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/repos"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            repo_json("old-site", true),
+            repo_json("tools", false),
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/repos"))
+        .and(query_param("per_page", "100"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header(
+                    "link",
+                    format!(
+                        r#"<{}/orgs/acme/repos?page=2>; rel="next", <{}/orgs/acme/repos?page=2>; rel="last""#,
+                        server.uri(),
+                        server.uri()
+                    )
+                    .as_str(),
+                )
+                .set_body_json(json!([repo_json("Website", false), repo_json("core", false)])),
+        )
+        .mount(&server)
+        .await;
+
+    // The repo has no file yet, so the finding goes to where the org is set
+    let check = UngovernedRepos::new(github(&server), Some("acme".into()))
+        .with_config_file("meta/validator.toml".into());
+    let diagnostics = check.run(&fixture("basic")).await;
+    assert_eq!(
+        summarize(&diagnostics),
+        [(Severity::Warning, "meta/validator.toml")]
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "Repo acme/tools is not governed; add repos/tools.toml for it"
+    );
+
+    let server = MockServer::start().await;
+    let check = UngovernedRepos::new(github(&server), Some("acme".into()));
+    let diagnostics = check.run(&fixture("basic")).await;
+    assert_eq!(
+        summarize(&diagnostics),
+        [(Severity::Warning, "validator.toml")]
+    );
+    assert!(diagnostics[0].inconclusive);
+}
+
+#[tokio::test]
+async fn repos_whose_file_failed_to_load_are_not_ungoverned() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/orgs/acme/repos"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            repo_json("Tools", false),
+            repo_json("website", false),
+        ])))
+        .mount(&server)
+        .await;
+
+    let governance = Governance::from_files(&[
+        ("repos/tools.toml".into(), b"name = 1\n".to_vec()),
+        ("repos/site.toml".into(), b"name = \"site\"\n".to_vec()),
+    ]);
+    assert_eq!(governance.load_errors().count(), 1);
+
+    let check = UngovernedRepos::new(github(&server), Some("acme".into()));
+    let diagnostics = check.run(&governance).await;
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect::<Vec<_>>(),
+        ["Repo acme/website is not governed; add repos/website.toml for it"]
+    );
+}
+
+#[test]
+fn org_checks_need_an_org() {
+    let client = GithubClient::new(http(), "http://unused", Some("token".into()));
+    assert_eq!(
+        GithubRepos::new(client.clone(), None)
+            .unavailable()
+            .as_deref(),
+        Some("no GitHub org configured")
+    );
+    assert!(
+        UngovernedRepos::new(client, Some("acme".into()))
+            .unavailable()
+            .is_none()
+    );
+}
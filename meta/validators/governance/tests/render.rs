@@ -0,0 +1,157 @@
+use governance::model::{
+    FileValidationMessages, Position, RetrySummary, SkippedCheck, Span, ValidationError,
+    ValidationReport, ValidationStatistics, ValidationWarning,
+};
+use governance::render::{Format, escape_data, escape_property, render};
+use governance::rules::{INVALID_FILE, Rule};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+const RULES: &[Rule] = &[INVALID_FILE];
+
+fn span(line: usize, column: usize, end_column: usize) -> Span {
+    Span {
+        start: Position { line, column },
+        end: Position {
+            line,
+            column: end_column,
+        },
+    }
+}
+
+/// One error with a span and characters that workflow commands must escape,
+/// and one warning without a span, in a file whose name needs escaping too.
+fn report() -> ValidationReport {
+    let mut files = BTreeMap::new();
+    files.insert(
+        "teams/a,b:c.toml".to_string(),
+        FileValidationMessages {
+            errors: vec![ValidationError {
+                rule: "invalid-file".into(),
+                file: "teams/a,b:c.toml".into(),
+                message: "100% broken\r\nsee: line 2, column 7".into(),
+                span: Some(span(2, 7, 10)),
+            }],
+            warnings: vec![ValidationWarning {
+                rule: "slack-identity".into(),
+                file: "teams/a,b:c.toml".into(),
+                message: "Slack member U01 is a bot".into(),
+                span: None,
+            }],
+        },
+    );
+    ValidationReport {
+        valid: false,
+        stats: ValidationStatistics {
+            contributors_count: 0,
+            teams_count: 1,
+            repos_count: 0,
+            valid_files_count: 0,
+            invalid_files_count: 1,
+            total_errors: 1,
+            total_warnings: 1,
+        },
+        files,
+        skipped_checks: vec![SkippedCheck {
+            check: "github-users".into(),
+            reason: "offline mode".into(),
+        }],
+        network: RetrySummary::default(),
+    }
+}
+
+fn rendered(format: Format, report: &ValidationReport) -> String {
+    let mut out = Vec::new();
+    render(format, report, RULES, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn workflow_command_data_escapes_percent_and_newlines() {
+    assert_eq!(escape_data("100%\r\ndone: a,b"), "100%25%0D%0Adone: a,b");
+    assert_eq!(escape_data("%0A"), "%250A");
+}
+
+#[test]
+fn workflow_command_properties_also_escape_separators() {
+    assert_eq!(
+        escape_property("100%\r\ndone: a,b"),
+        "100%25%0D%0Adone%3A a%2Cb"
+    );
+}
+
+#[test]
+fn github_annotations_are_escaped() {
+    let output = rendered(Format::Github, &report());
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        [
+            "::group::teams/a,b:c.toml",
+            "::error file=teams/a%2Cb%3Ac.toml,line=2,col=7,endLine=2,endColumn=10,\
+             title=invalid-file::100%25 broken%0D%0Asee: line 2, column 7",
+            "::warning file=teams/a%2Cb%3Ac.toml,title=slack-identity::Slack member U01 is a bot",
+            "::endgroup::",
+            "::notice title=github-users::Skipped github-users: offline mode",
+            "1 error(s) and 1 warning(s) across 0 contributor(s), 1 team(s) and 0 repo(s)",
+        ]
+    );
+}
+
+#[test]
+fn sarif_results_carry_rules_levels_and_regions() {
+    let sarif: Value = serde_json::from_str(&rendered(Format::Sarif, &report())).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "invalid-file");
+    // Span columns count characters, so SARIF must not read them as UTF-16
+    assert_eq!(run["columnKind"], "unicodeCodePoints");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+
+    let error = &results[0];
+    assert_eq!(error["ruleId"], "invalid-file");
+    assert_eq!(error["ruleIndex"], 0);
+    assert_eq!(error["level"], "error");
+    assert_eq!(
+        error["message"]["text"],
+        "100% broken\r\nsee: line 2, column 7"
+    );
+    let location = &error["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "teams/a,b:c.toml");
+    assert_eq!(
+        location["region"],
+        serde_json::json!({ "startLine": 2, "startColumn": 7, "endLine": 2, "endColumn": 10 })
+    );
+
+    // Unknown rules get no index, and findings without a span are pinned to
+    // the top of the file
+    let warning = &results[1];
+    assert_eq!(warning["level"], "warning");
+    assert!(warning.get("ruleIndex").is_none());
+    assert_eq!(
+        warning["locations"][0]["physicalLocation"]["region"],
+        serde_json::json!({ "startLine": 1 })
+    );
+
+    let notifications = &run["invocations"][0]["toolExecutionNotifications"];
+    assert_eq!(
+        notifications[0]["message"]["text"],
+        "Skipped github-users: offline mode"
+    );
+}
+
+#[test]
+fn json_is_the_report_itself() {
+    let report = report();
+    let json: Value = serde_json::from_str(&rendered(Format::Json, &report)).unwrap();
+    assert_eq!(json, serde_json::to_value(&report).unwrap());
+
+    let messages = &json["files"]["teams/a,b:c.toml"];
+    assert_eq!(messages["errors"][0]["span"]["start"]["column"], 7);
+    assert!(messages["warnings"][0].get("span").is_none());
+    // Empty network summaries are left out
+    assert!(json.get("network").is_none());
+}
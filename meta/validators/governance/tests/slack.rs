@@ -0,0 +1,339 @@
+mod common;
+
+use common::{fixture, slack};
+use governance::checks::{
+    AsyncCheck, SlackChannelMembers, SlackChannels, SlackIdentities, SlackMembers,
+};
+use governance::model::{Diagnostic, Severity};
+use governance::remote::SlackStrategy;
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn page(items: &str, objects: Value, next_cursor: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "ok": true,
+        items: objects,
+        "response_metadata": { "next_cursor": next_cursor },
+    }))
+}
+
+/// `(severity, message)` pairs, in a stable order.
+fn summarize(diagnostics: Vec<Diagnostic>) -> Vec<(Severity, String)> {
+    let mut summary = diagnostics
+        .into_iter()
+        .map(|d| (d.severity, d.message))
+        .collect::<Vec<_>>();
+    summary.sort_by(|a, b| a.1.cmp(&b.1));
+    summary
+}
+
+#[tokio::test]
+async fn members_are_checked_against_one_paged_listing() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users.list"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(page(
+            "members",
+            json!([{ "id": "U01ALICE", "deleted": false }]),
+            "page-2",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users.list"))
+        .and(query_param("cursor", "page-2"))
+        .respond_with(page(
+            "members",
+            json!([{ "id": "U01BOB", "deleted": true }]),
+            "",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let diagnostics = SlackMembers::new(slack(&server).with_strategy(SlackStrategy::List))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(diagnostics),
+        [(
+            Severity::Warning,
+            "Slack member U01BOB is deactivated".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn channels_missing_from_the_listing_are_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/conversations.list"))
+        .and(query_param("exclude_archived", "false"))
+        .respond_with(page(
+            "channels",
+            json!([{ "id": "C01OTHER", "is_archived": true }]),
+            "",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let diagnostics = SlackChannels::new(slack(&server).with_strategy(SlackStrategy::List))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(diagnostics),
+        [(
+            Severity::Error,
+            "Slack channel ID does not exist: C01CORE".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn a_failed_listing_is_inconclusive_for_every_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users.list"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "ok": false, "error": "invalid_auth" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let diagnostics = SlackMembers::new(slack(&server).with_strategy(SlackStrategy::List))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(diagnostics.len(), 2);
+    for diagnostic in diagnostics {
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert!(diagnostic.message.contains("Invalid authentication"));
+    }
+}
+
+#[tokio::test]
+async fn lookups_one_at_a_time_also_see_archived_channels() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/conversations.info"))
+        .and(query_param("channel", "C01CORE"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "ok": true,
+            "channel": { "id": "C01CORE", "is_archived": true },
+        })))
+        .mount(&server)
+        .await;
+
+    let diagnostics = SlackChannels::new(slack(&server).with_strategy(SlackStrategy::Info))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(diagnostics),
+        [(
+            Severity::Warning,
+            "Slack channel C01CORE is archived".to_string()
+        )]
+    );
+}
+
+async fn users(server: &MockServer, members: Value) {
+    Mock::given(method("GET"))
+        .and(path("/users.list"))
+        .respond_with(page("members", members, ""))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn identities_report_bots_and_other_peoples_profiles() {
+    let server = MockServer::start().await;
+    users(
+        &server,
+        json!([
+            {
+                "id": "U01ALICE",
+                "is_bot": true,
+                "profile": { "real_name": "Deploy Bot", "display_name": "" },
+            },
+            {
+                "id": "U01BOB",
+                "profile": { "real_name": "Robert Jones", "display_name": "rj" },
+            },
+        ]),
+    )
+    .await;
+
+    let diagnostics = SlackIdentities::new(slack(&server).with_strategy(SlackStrategy::List))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(diagnostics),
+        [
+            (
+                Severity::Warning,
+                "Full name 'Bob Example' doesn't match the Slack profile of U01BOB \
+                 ('Robert Jones', shown as 'rj')"
+                    .to_string()
+            ),
+            (
+                Severity::Warning,
+                "Slack member U01ALICE is a bot account ('Deploy Bot')".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn deactivated_accounts_are_only_reported_as_unknown_members() {
+    let server = MockServer::start().await;
+    users(
+        &server,
+        json!([
+            {
+                "id": "U01ALICE",
+                "deleted": true,
+                "profile": { "real_name": "Someone Else", "display_name": "" },
+            },
+            {
+                "id": "U01BOB",
+                "profile": { "real_name": "Bob Example", "display_name": "" },
+            },
+        ]),
+    )
+    .await;
+
+    let client = slack(&server).with_strategy(SlackStrategy::List);
+    let members = SlackMembers::new(client.clone())
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(members),
+        [(
+            Severity::Warning,
+            "Slack member U01ALICE is deactivated".to_string()
+        )]
+    );
+
+    // Not even the name mismatch is reported for it
+    let identities = SlackIdentities::new(client).run(&fixture("basic")).await;
+    assert!(identities.is_empty(), "{:?}", identities);
+}
+
+#[tokio::test]
+async fn identities_report_bots_even_under_the_right_name() {
+    let server = MockServer::start().await;
+    for (user, body) in [
+        (
+            "U01ALICE",
+            json!({ "id": "U01ALICE", "is_bot": true, "profile": { "real_name": "Alice Example" } }),
+        ),
+        // Slackbot isn't flagged as a bot
+        (
+            "U01BOB",
+            json!({ "id": "USLACKBOT", "profile": { "real_name": "Bob Example" } }),
+        ),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/users.info"))
+            .and(query_param("user", user))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "ok": true, "user": body })),
+            )
+            .mount(&server)
+            .await;
+    }
+
+    let diagnostics = SlackIdentities::new(slack(&server).with_strategy(SlackStrategy::Info))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(
+        summarize(diagnostics),
+        [
+            (
+                Severity::Warning,
+                "Slack member U01ALICE is a bot account ('Alice Example')".to_string()
+            ),
+            (
+                Severity::Warning,
+                "Slack member U01BOB is a bot account ('Bob Example')".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn identities_allow_for_how_people_write_their_names() {
+    let server = MockServer::start().await;
+    users(
+        &server,
+        json!([
+            {
+                "id": "U01ALICE",
+                "profile": { "real_name": "", "display_name": "alice.example" },
+            },
+            {
+                "id": "U01BOB",
+                "profile": { "real_name": "Bob Exmple", "display_name": "" },
+            },
+        ]),
+    )
+    .await;
+
+    let diagnostics = SlackIdentities::new(slack(&server).with_strategy(SlackStrategy::List))
+        .run(&fixture("basic"))
+        .await;
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[tokio::test]
+async fn team_members_missing_from_their_channels_are_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/conversations.members"))
+        .and(query_param("channel", "C01CORE"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(page("members", json!(["U01CAROL"]), "page-2"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/conversations.members"))
+        .and(query_param("cursor", "page-2"))
+        .respond_with(page("members", json!(["U01ALICE"]), ""))
+        .mount(&server)
+        .await;
+
+    let diagnostics = SlackChannelMembers::new(slack(&server).with_strategy(SlackStrategy::Info))
+        .run(&fixture("basic"))
+        .await;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].file, "teams/core.toml");
+    assert_eq!(
+        diagnostics[0].message,
+        "Team 'core' member 'bob' is not in Slack channel C01CORE"
+    );
+    assert_eq!(diagnostics[0].span.map(|span| span.start.line), Some(4));
+}
+
+#[tokio::test]
+async fn channel_membership_skips_missing_channels() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/conversations.members"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "ok": false, "error": "channel_not_found" })),
+        )
+        .mount(&server)
+        .await;
+
+    let diagnostics = SlackChannelMembers::new(slack(&server).with_strategy(SlackStrategy::Info))
+        .run(&fixture("basic"))
+        .await;
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
@@ -1,10 +1,8 @@
+use governance::Governance;
 use governance::model::{Contributor, EntityKey, Repo, Team};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use std::{
-    collections::{HashMap, HashSet},
-    error::Error,
-};
+use std::error::Error;
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "nodeType")]
@@ -41,22 +39,12 @@ struct GraphData {
 }
 
 struct GraphBuilder<'a> {
-    contributors: &'a HashMap<EntityKey, Contributor>,
-    teams: &'a HashMap<EntityKey, Team>,
-    repos: &'a HashMap<EntityKey, Repo>,
+    governance: &'a Governance,
 }
 
 impl<'a> GraphBuilder<'a> {
-    fn new(
-        contributors: &'a HashMap<EntityKey, Contributor>,
-        teams: &'a HashMap<EntityKey, Team>,
-        repos: &'a HashMap<EntityKey, Repo>,
-    ) -> Self {
-        Self {
-            contributors,
-            teams,
-            repos,
-        }
+    fn new(governance: &'a Governance) -> Self {
+        Self { governance }
     }
 
     fn build_contributors_teams_graph(&self) -> GraphData {
@@ -64,7 +52,7 @@ impl<'a> GraphBuilder<'a> {
         let mut links = Vec::new();
 
         // Add contributor nodes
-        for (id, contributor) in self.contributors {
+        for (id, contributor) in self.governance.contributors() {
             nodes.push(GraphNode::Contributor {
                 id: id.scoped_id(),
                 inner: contributor.clone(),
@@ -72,17 +60,14 @@ impl<'a> GraphBuilder<'a> {
         }
 
         // Add team nodes and links
-        for (id, team) in self.teams {
+        for (id, team) in self.governance.teams() {
             nodes.push(GraphNode::Team {
                 id: id.scoped_id(),
                 inner: team.clone(),
             });
 
             for member_id in &team.members {
-                let target_id = EntityKey {
-                    kind: "contributor".to_string(),
-                    name: member_id.clone(),
-                };
+                let target_id = EntityKey::contributor(member_id);
 
                 links.push(GraphLink {
                     source: id.scoped_id(),
@@ -100,7 +85,7 @@ impl<'a> GraphBuilder<'a> {
         let mut links = Vec::new();
 
         // Add team nodes
-        for (id, team) in self.teams {
+        for (id, team) in self.governance.teams() {
             nodes.push(GraphNode::Team {
                 id: id.scoped_id(),
                 inner: team.clone(),
@@ -108,7 +93,7 @@ impl<'a> GraphBuilder<'a> {
         }
 
         // Add repo nodes
-        for (id, repo) in self.repos {
+        for (id, repo) in self.governance.repos() {
             nodes.push(GraphNode::Repo {
                 id: id.scoped_id(),
                 inner: repo.clone(),
@@ -116,12 +101,9 @@ impl<'a> GraphBuilder<'a> {
         }
 
         // Add team-repo links
-        for (team_id, team) in self.teams {
+        for (team_id, team) in self.governance.teams() {
             for repo_id in &team.repos {
-                let target_id = EntityKey {
-                    kind: "repo".to_string(),
-                    name: repo_id.clone(),
-                };
+                let target_id = EntityKey::repo(repo_id);
 
                 links.push(GraphLink {
                     source: team_id.scoped_id(),
@@ -138,23 +120,8 @@ impl<'a> GraphBuilder<'a> {
         let mut nodes = Vec::new();
         let mut links = Vec::new();
 
-        // Map to track which repos each contributor is connected to (via teams)
-        let mut contributor_to_repos: HashMap<String, HashSet<String>> = HashMap::new();
-
-        // Build the contributor-team-repo connection map
-        for team in self.teams.values() {
-            for member_id in &team.members {
-                for repo_id in &team.repos {
-                    contributor_to_repos
-                        .entry(member_id.clone())
-                        .or_default()
-                        .insert(repo_id.clone());
-                }
-            }
-        }
-
         // Add contributor nodes
-        for (id, contributor) in self.contributors {
+        for (id, contributor) in self.governance.contributors() {
             nodes.push(GraphNode::Contributor {
                 id: id.scoped_id(),
                 inner: contributor.clone(),
@@ -162,29 +129,19 @@ impl<'a> GraphBuilder<'a> {
         }
 
         // Add repo nodes
-        for (id, repo) in self.repos {
+        for (id, repo) in self.governance.repos() {
             nodes.push(GraphNode::Repo {
                 id: id.scoped_id(),
                 inner: repo.clone(),
             });
         }
 
-        // Add contributor-repo links
-        for (contributor_id, repo_ids) in contributor_to_repos {
-            for repo_id in repo_ids {
-                let source_id = EntityKey {
-                    kind: "contributor".to_string(),
-                    name: contributor_id.clone(),
-                };
-
-                let target_id = EntityKey {
-                    kind: "repo".to_string(),
-                    name: repo_id.clone(),
-                };
-
+        // Add contributor-repo links, connected through the contributor's teams
+        for (contributor_id, _) in self.governance.contributors() {
+            for repo_id in self.governance.repos_of(&contributor_id.name) {
                 links.push(GraphLink {
-                    source: source_id.scoped_id(),
-                    target: target_id.scoped_id(),
+                    source: contributor_id.scoped_id(),
+                    target: repo_id.scoped_id(),
                     link_type: "contributor-repo".to_string(),
                 });
             }
@@ -194,13 +151,9 @@ impl<'a> GraphBuilder<'a> {
     }
 }
 
-pub fn build_graph_data(
-    contributors: HashMap<EntityKey, Contributor>,
-    teams: HashMap<EntityKey, Team>,
-    repos: HashMap<EntityKey, Repo>,
-) -> Result<Value, Box<dyn Error>> {
+pub fn build_graph_data(governance: &Governance) -> Result<Value, Box<dyn Error>> {
     // Build filtered views
-    let builder = GraphBuilder::new(&contributors, &teams, &repos);
+    let builder = GraphBuilder::new(governance);
 
     Ok(json!({
         "default": builder.build_contributors_teams_graph(),
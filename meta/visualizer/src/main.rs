@@ -1,10 +1,12 @@
 mod graph;
 
 use askama::Template;
-use governance::loader::{load_contributors, load_repos, load_teams};
+use clap::Parser;
+use governance::Governance;
+use governance::loader::resolve_root;
 use graph::build_graph_data;
 use serde_json::Value;
-use std::{error::Error, fs, path::Path, process};
+use std::{error::Error, fs, path::PathBuf, process};
 
 #[derive(Template)]
 #[template(path = "index.html")]
@@ -12,28 +14,39 @@ struct GovernanceTemplate {
     graph_data: Value,
 }
 
+#[derive(Parser)]
+#[command(about = "Renders the governance graph to dist/index.html")]
+struct Cli {
+    /// Governance root directory (defaults to searching upward from the current directory)
+    #[arg(long)]
+    root: Option<PathBuf>,
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
-    // Ensure this is being run from the workspace root
-    if !Path::new("contributors").exists() {
-        eprintln!("Please run this binary from the workspace root.");
-        process::exit(1);
-    }
+    let cli = Cli::parse();
+    let root = resolve_root(cli.root.as_deref())?;
 
     // Create output directory
-    let dist_dir = Path::new("dist");
-    fs::create_dir_all(dist_dir)?;
+    let dist_dir = root.join("dist");
+    fs::create_dir_all(&dist_dir)?;
 
     // Load governance data
-    let contributors = load_contributors()?;
-    let teams = load_teams()?;
-    let repos = load_repos()?;
+    let governance = Governance::load(&root)?;
+
+    let load_errors = governance.load_errors().collect::<Vec<_>>();
+    if !load_errors.is_empty() {
+        for error in &load_errors {
+            eprintln!("{}: {}", error.file, error.message);
+        }
+        process::exit(1);
+    }
 
-    let graph_data = build_graph_data(contributors, teams, repos)?;
+    let graph_data = build_graph_data(&governance)?;
 
     // Render template
     let template = GovernanceTemplate { graph_data };
     let html = template.render()?;
 
-    fs::write("dist/index.html", html)?;
+    fs::write(dist_dir.join("index.html"), html)?;
     Ok(())
 }